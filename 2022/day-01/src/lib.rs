@@ -0,0 +1,48 @@
+use aoc_common::{blocks, lines, parse_all, unsigned, Answer, Day, ParseError, Rng, Solution};
+use eyre::Result;
+
+pub const DAY: Day = Day::new::<CalorieCounting>(1, "Calorie Counting", env!("CARGO_MANIFEST_DIR"))
+    .with_example(include_str!("../example"))
+    .with_generator(generate);
+
+pub fn calories_per_elf(input: &str) -> Result<Vec<u32>, ParseError> {
+    let elf = |cursor: &mut _| Ok(lines(unsigned::<u32>)(cursor)?.into_iter().sum());
+    parse_all(input, blocks(elf))
+}
+
+pub fn sum_of_best(mut sums: Vec<u32>, count: usize) -> u32 {
+    sums.sort_unstable();
+    sums.iter().rev().take(count).sum()
+}
+
+pub struct CalorieCounting {
+    calories: Vec<u32>,
+}
+
+impl Solution for CalorieCounting {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            calories: calories_per_elf(input)?,
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        Ok(sum_of_best(self.calories.clone(), 1).into())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        Ok(sum_of_best(self.calories.clone(), 3).into())
+    }
+}
+
+/// Generates `size` elves with up to 15 snacks each; real inputs have about 250 elves.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let elves: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            (0..rng.range(1..=15))
+                .map(|_| format!("{}\n", rng.range(1_000..=60_000)))
+                .collect()
+        })
+        .collect();
+    elves.join("\n")
+}
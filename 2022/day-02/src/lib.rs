@@ -0,0 +1,179 @@
+use aoc_common::{
+    lines, literal, one_of, parse_all, Answer, Cursor, Day, ParseError, Rng, Solution,
+};
+
+pub const DAY: Day =
+    Day::new::<RockPaperScissors>(2, "Rock Paper Scissors", env!("CARGO_MANIFEST_DIR"))
+        .with_example(include_str!("../example"))
+        .with_generator(generate);
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Symbol {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Symbol {
+    pub fn score(self) -> u32 {
+        match self {
+            Symbol::Rock => 1,
+            Symbol::Paper => 2,
+            Symbol::Scissors => 3,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl Outcome {
+    pub fn score(self) -> u32 {
+        match self {
+            Outcome::Win => 6,
+            Outcome::Loss => 0,
+            Outcome::Draw => 3,
+        }
+    }
+}
+
+/// The second column of a round, which part 1 reads as a symbol and part 2 as an outcome.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Column {
+    X,
+    Y,
+    Z,
+}
+
+impl Column {
+    pub fn symbol(self) -> Symbol {
+        match self {
+            Column::X => Symbol::Rock,
+            Column::Y => Symbol::Paper,
+            Column::Z => Symbol::Scissors,
+        }
+    }
+
+    pub fn outcome(self) -> Outcome {
+        match self {
+            Column::X => Outcome::Loss,
+            Column::Y => Outcome::Draw,
+            Column::Z => Outcome::Win,
+        }
+    }
+}
+
+pub fn play(opponent: Symbol, player: Symbol) -> Outcome {
+    match (opponent, player) {
+        (a, b) if a == b => Outcome::Draw,
+        (Symbol::Rock, Symbol::Paper)
+        | (Symbol::Paper, Symbol::Scissors)
+        | (Symbol::Scissors, Symbol::Rock) => Outcome::Win,
+        _ => Outcome::Loss,
+    }
+}
+
+pub fn symbol_to_play(opponent: Symbol, outcome: Outcome) -> Symbol {
+    match (opponent, outcome) {
+        (_, Outcome::Draw) => opponent,
+        (Symbol::Scissors, Outcome::Win) | (Symbol::Paper, Outcome::Loss) => Symbol::Rock,
+        (Symbol::Rock, Outcome::Win) | (Symbol::Scissors, Outcome::Loss) => Symbol::Paper,
+        (Symbol::Paper, Outcome::Win) | (Symbol::Rock, Outcome::Loss) => Symbol::Scissors,
+    }
+}
+
+pub fn parse_game(cursor: &mut Cursor) -> Result<(Symbol, Column), ParseError> {
+    let opponent = one_of(&[
+        ("A", Symbol::Rock),
+        ("B", Symbol::Paper),
+        ("C", Symbol::Scissors),
+    ])(cursor)?;
+    literal(" ")(cursor)?;
+    let second = one_of(&[("X", Column::X), ("Y", Column::Y), ("Z", Column::Z)])(cursor)?;
+    Ok((opponent, second))
+}
+
+pub fn parse_games(input: &str) -> Result<Vec<(Symbol, Column)>, ParseError> {
+    parse_all(input, lines(parse_game))
+}
+
+pub fn task1_score((opponent, second): (Symbol, Column)) -> u32 {
+    let player = second.symbol();
+    player.score() + play(opponent, player).score()
+}
+
+pub fn task2_score((opponent, second): (Symbol, Column)) -> u32 {
+    let outcome = second.outcome();
+    symbol_to_play(opponent, outcome).score() + outcome.score()
+}
+
+pub struct RockPaperScissors {
+    games: Vec<(Symbol, Column)>,
+}
+
+impl Solution for RockPaperScissors {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            games: parse_games(input)?,
+        })
+    }
+
+    fn part_1(&self) -> eyre::Result<Answer> {
+        let score: u32 = self.games.iter().copied().map(task1_score).sum();
+        Ok(score.into())
+    }
+
+    fn part_2(&self) -> eyre::Result<Answer> {
+        let score: u32 = self.games.iter().copied().map(task2_score).sum();
+        Ok(score.into())
+    }
+}
+
+/// Generates `size` rounds; real inputs have 2500.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let opponent = rng.choose(&['A', 'B', 'C']);
+            let second = rng.choose(&['X', 'Y', 'Z']);
+            format!("{opponent} {second}\n")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example_task_1() {
+        assert_eq!(task1_score((Symbol::Rock, Column::Y)), 8);
+        assert_eq!(task1_score((Symbol::Paper, Column::X)), 1);
+        assert_eq!(task1_score((Symbol::Scissors, Column::Z)), 6);
+    }
+
+    #[test]
+    fn invalid_rounds() {
+        let error = parse_games("A Y\nB W\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+
+        let error = parse_games("A Y\nC\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
+    #[test]
+    fn example_task_2() {
+        assert_eq!(task2_score((Symbol::Rock, Column::Y)), 4);
+        assert_eq!(task2_score((Symbol::Paper, Column::X)), 1);
+        assert_eq!(task2_score((Symbol::Scissors, Column::Z)), 7);
+    }
+
+    #[test]
+    fn generated() {
+        let games = parse_games(&generate(&mut Rng::new(1), 100)).unwrap();
+        assert_eq!(games.len(), 100);
+    }
+}
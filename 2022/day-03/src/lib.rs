@@ -0,0 +1,199 @@
+use std::collections::{hash_map::RandomState, HashSet};
+
+use aoc_common::{lines, parse_all, take_while, Answer, Cursor, Day, ParseError, Rng, Solution};
+use eyre::{eyre, Result};
+
+pub const DAY: Day =
+    Day::new::<RucksackReorganization>(3, "Rucksack Reorganization", env!("CARGO_MANIFEST_DIR"))
+        .with_example(include_str!("../example"))
+        .with_generator(generate);
+
+pub fn shared_items_backpack(backpack: &str) -> Vec<char> {
+    let pieces = backpack.split_at(backpack.len() / 2);
+    let compartment1: HashSet<_, RandomState> = HashSet::from_iter(pieces.0.chars());
+    let compartment2 = HashSet::from_iter(pieces.1.chars());
+
+    compartment1.intersection(&compartment2).cloned().collect()
+}
+
+/// Returns `None` if the elves share no item.
+pub fn shared_item_elves(elves: &[&str]) -> Option<char> {
+    let shared = elves
+        .iter()
+        .map(|backpack| HashSet::from_iter(backpack.chars()))
+        .reduce(|a: HashSet<_, RandomState>, b| a.intersection(&b).cloned().collect())?;
+
+    shared.iter().next().copied()
+}
+
+pub fn score(item: char) -> u32 {
+    let ordinal = item as u32;
+    match item {
+        'a'..='z' => ordinal - 'a' as u32 + 1,
+        'A'..='Z' => ordinal - 'A' as u32 + 27,
+        _ => panic!("Invalid character"),
+    }
+}
+
+pub fn parse_backpack(cursor: &mut Cursor) -> Result<String, ParseError> {
+    let items = take_while(|item| item.is_ascii_alphabetic())(cursor)?;
+    if items.is_empty() && cursor.is_at_end() {
+        return Err(cursor.expected("a backpack with items"));
+    }
+    if !cursor.is_at_end() {
+        return Err(cursor.error("items have to be letters"));
+    }
+    if !items.len().is_multiple_of(2) {
+        return Err(cursor.error_at(items, "backpack does not split into two equal compartments"));
+    }
+
+    Ok(items.to_string())
+}
+
+/// Parses one backpack per line, in whole groups of three elves.
+pub fn parse_backpacks(input: &str) -> Result<Vec<String>, ParseError> {
+    let backpacks = parse_all(input, lines(parse_backpack))?;
+    let complete = backpacks.len() - backpacks.len() % 3;
+    if let Some(first_of_group) = input.lines().nth(complete) {
+        return Err(ParseError::new(
+            input,
+            first_of_group,
+            "the last group has fewer than three elves",
+        ));
+    }
+
+    Ok(backpacks)
+}
+
+pub struct RucksackReorganization {
+    backpacks: Vec<String>,
+}
+
+impl Solution for RucksackReorganization {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            backpacks: parse_backpacks(input)?,
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        let scores = self
+            .backpacks
+            .iter()
+            .map(|backpack| shared_items_backpack(backpack))
+            .flat_map(|shared| shared.into_iter().map(score));
+        Ok(scores.sum::<u32>().into())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        let backpacks: Vec<&str> = self.backpacks.iter().map(String::as_str).collect();
+        let mut total = 0;
+        for (index, group) in backpacks.chunks(3).enumerate() {
+            let badge = shared_item_elves(group)
+                .ok_or_else(|| eyre!("group {} shares no item", index + 1))?;
+            total += score(badge);
+        }
+        Ok(total.into())
+    }
+}
+
+/// Generates `size` backpacks, rounded up to whole groups of three; real inputs have 300.
+///
+/// Every backpack shares exactly one item between its compartments and every group exactly
+/// one badge, because the elves of a group pack from disjoint sets of items.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut letters: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let mut output = String::new();
+
+    for _ in 0..size.div_ceil(3).max(1) {
+        rng.shuffle(&mut letters);
+        let (badge, rest) = letters.split_first().expect("there are 52 letters");
+        for pool in rest.chunks(rest.len() / 3) {
+            let shared = if rng.chance(0.2) {
+                *badge
+            } else {
+                rng.choose(pool)
+            };
+            let others: Vec<char> = pool
+                .iter()
+                .copied()
+                .filter(|item| *item != shared)
+                .collect();
+            let (left_items, right_items) = others.split_at(others.len() / 2);
+
+            let length = rng.index(12) + 2;
+            let mut compartments = [left_items, right_items].map(|items| {
+                let mut compartment = vec![shared];
+                compartment.extend((1..length).map(|_| rng.choose(items)));
+                compartment
+            });
+            if shared != *badge {
+                let side = rng.index(2);
+                let slot = rng.index(length - 1) + 1;
+                compartments[side][slot] = *badge;
+            }
+            for compartment in &mut compartments {
+                rng.shuffle(compartment);
+                output.extend(compartment.iter());
+            }
+            output.push('\n');
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn invalid_backpacks() {
+        let error =
+            parse_all("vJrwpWtwJgWrhcsFMMfFFhFp\nab1d\n", lines(parse_backpack)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+
+        assert!(parse_all("abc", parse_backpack).is_err());
+
+        let error = parse_backpacks("\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        let error = parse_backpacks("ab\ncd\n\nef\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+
+        let error = parse_backpacks("ab\ncd\nef\ngh\nij\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+        assert!(parse_backpacks("ab\nca\nad\n").is_ok());
+    }
+
+    #[test]
+    fn score_correct() {
+        assert_eq!(score('a'), 1);
+        assert_eq!(score('z'), 26);
+        assert_eq!(score('A'), 27);
+        assert_eq!(score('Z'), 52);
+
+        assert_eq!(score('p'), 16);
+        assert_eq!(score('L'), 38);
+        assert_eq!(score('P'), 42);
+        assert_eq!(score('v'), 22);
+        assert_eq!(score('t'), 20);
+        assert_eq!(score('s'), 19);
+    }
+
+    #[test]
+    fn generated() {
+        let input = generate(&mut Rng::new(1), 100);
+        let backpacks = parse_backpacks(&input).unwrap();
+        assert_eq!(backpacks.len(), 102);
+        for group in backpacks.chunks(3) {
+            for backpack in group {
+                assert_eq!(shared_items_backpack(backpack).len(), 1, "{backpack}");
+            }
+            let items = group
+                .iter()
+                .map(|backpack| HashSet::<char>::from_iter(backpack.chars()))
+                .reduce(|a, b| a.intersection(&b).copied().collect())
+                .unwrap();
+            assert_eq!(items.len(), 1, "{group:?}");
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use std::ops::RangeInclusive;
+
+use aoc_common::{
+    lines, literal, parse_all, unsigned, Answer, Cursor, Day, ParseError, Rng, Solution,
+};
+use eyre::Result;
+
+pub const DAY: Day = Day::new::<CampCleanup>(4, "Camp Cleanup", env!("CARGO_MANIFEST_DIR"))
+    .with_example(include_str!("../example"))
+    .with_generator(generate);
+
+/// Parses a range like `2-4`.
+pub fn parse_range(cursor: &mut Cursor) -> Result<RangeInclusive<u32>, ParseError> {
+    let start = unsigned(cursor)?;
+    literal("-")(cursor)?;
+    let end = unsigned(cursor)?;
+
+    Ok(start..=end)
+}
+
+pub fn parse_assignment_pairs(
+    cursor: &mut Cursor,
+) -> Result<(RangeInclusive<u32>, RangeInclusive<u32>), ParseError> {
+    let first = parse_range(cursor)?;
+    literal(",")(cursor)?;
+    Ok((first, parse_range(cursor)?))
+}
+
+pub fn range_contains_other(a: &RangeInclusive<u32>, b: &RangeInclusive<u32>) -> bool {
+    let a_contains_b = a.contains(b.start()) && a.contains(b.end());
+    let b_contains_a = b.contains(a.start()) && b.contains(a.end());
+
+    a_contains_b || b_contains_a
+}
+
+pub fn ranges_overlap(a: &RangeInclusive<u32>, b: &RangeInclusive<u32>) -> bool {
+    let a_contains_b = a.contains(b.start()) || a.contains(b.end());
+    let b_contains_a = b.contains(a.start()) || b.contains(a.end());
+
+    a_contains_b || b_contains_a
+}
+
+pub struct CampCleanup {
+    assignments: Vec<(RangeInclusive<u32>, RangeInclusive<u32>)>,
+}
+
+impl Solution for CampCleanup {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            assignments: parse_all(input, lines(parse_assignment_pairs))?,
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        let overlaps = self
+            .assignments
+            .iter()
+            .filter(|assignments| range_contains_other(&assignments.0, &assignments.1))
+            .count();
+        Ok(overlaps.into())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        let overlaps = self
+            .assignments
+            .iter()
+            .filter(|assignments| ranges_overlap(&assignments.0, &assignments.1))
+            .count();
+        Ok(overlaps.into())
+    }
+}
+
+/// Generates `size` pairs of assignments; real inputs have 1000.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let [first, second] = [(); 2].map(|_| {
+                let start = rng.range(1..=99);
+                format!("{start}-{}", rng.range(start..=99))
+            });
+            format!("{first},{second}\n")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn range_parsing() {
+        assert_eq!(parse_all("0-1", parse_range), Ok(0..=1));
+        assert_eq!(parse_all("25-103", parse_range), Ok(25..=103));
+        assert_eq!(parse_all("0-0", parse_range), Ok(0..=0));
+        assert_eq!(parse_all("100-100", parse_range), Ok(100..=100));
+    }
+
+    #[test]
+    fn invalid_assignments() {
+        let error = parse_all("2-4,6-8\n2-3,4-x\n", lines(parse_assignment_pairs)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 7));
+
+        let error = parse_all("2-4", parse_assignment_pairs).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+    }
+
+    #[test]
+    fn generated() {
+        let input = generate(&mut Rng::new(1), 100);
+        let assignments = parse_all(&input, lines(parse_assignment_pairs)).unwrap();
+        assert_eq!(assignments.len(), 100);
+        assert!(assignments
+            .iter()
+            .all(|(a, b)| a.start() <= a.end() && b.start() <= b.end()));
+    }
+}
@@ -0,0 +1,225 @@
+use aoc_common::{
+    character, labelled, line, lines, literal, parse_all, separated, unsigned, Answer, Cursor, Day,
+    ParseError, Parser, Rng, Solution,
+};
+use eyre::{OptionExt, Result};
+
+pub const DAY: Day = Day::new::<SupplyStacks>(5, "Supply Stacks", env!("CARGO_MANIFEST_DIR"))
+    .with_example(include_str!("../example"))
+    .with_generator(generate);
+
+/// Parses a slot in a row of the drawing, either a crate like `[A]` or blank.
+fn parse_slot(cursor: &mut Cursor) -> Result<Option<char>, ParseError> {
+    if cursor.eat("   ") {
+        return Ok(None);
+    }
+    if !cursor.eat("[") {
+        return Err(cursor.expected("a crate like '[A]'"));
+    }
+    let item = character("a letter", |item| {
+        item.is_ascii_alphabetic().then_some(item)
+    })(cursor)?;
+    literal("]")(cursor)?;
+    Ok(Some(item))
+}
+
+/// Parses a number in the line below the crates, like ` 1 `.
+fn parse_stack_number(cursor: &mut Cursor) -> Result<usize, ParseError> {
+    literal(" ")(cursor)?;
+    let number = unsigned(cursor)?;
+    cursor.eat(" ");
+    Ok(number)
+}
+
+/// Parses the drawing of the stacks up to and including the line of stack numbers.
+pub fn parse_stacks(cursor: &mut Cursor) -> Result<Vec<Vec<char>>, ParseError> {
+    let mut rows = Vec::new();
+    while !cursor.is_at_end()
+        && !cursor
+            .rest()
+            .trim_start_matches(' ')
+            .starts_with(|next: char| next.is_ascii_digit())
+    {
+        rows.push(line(separated(parse_slot, " "))(cursor)?);
+    }
+    if rows.is_empty() {
+        return Err(cursor.expected("crates above a line of stack numbers"));
+    }
+    line(separated(parse_stack_number, " "))(cursor)?;
+
+    let mut stacks = Vec::new();
+    for row in rows.iter().rev() {
+        stacks.resize(stacks.len().max(row.len()), Vec::new());
+        for (stack, slot) in stacks.iter_mut().zip(row) {
+            stack.extend(slot);
+        }
+    }
+    Ok(stacks)
+}
+
+#[derive(Debug)]
+pub struct Command {
+    pub count: usize,
+    pub source: usize,
+    pub destination: usize,
+}
+
+pub fn parse_command(cursor: &mut Cursor) -> Result<Command, ParseError> {
+    Ok(Command {
+        count: labelled("move ", unsigned)(cursor)?,
+        source: labelled(" from ", unsigned)(cursor)?,
+        destination: labelled(" to ", unsigned)(cursor)?,
+    })
+}
+
+pub fn parse_commands<'a>(stack_count: usize) -> impl Parser<'a, Vec<Command>> {
+    lines(move |cursor: &mut Cursor<'a>| {
+        let start = cursor.rest();
+        let command = parse_command(cursor)?;
+        for stack in [command.source, command.destination] {
+            if !(1..=stack_count).contains(&stack) {
+                return Err(cursor.error_at(
+                    start,
+                    format!("stack {stack} does not exist, there are {stack_count} stacks"),
+                ));
+            }
+        }
+        Ok(command)
+    })
+}
+
+/// Parses the drawing of the stacks and, after a blank line, the commands.
+pub fn parse_input(input: &str) -> Result<(Vec<Vec<char>>, Vec<Command>), ParseError> {
+    parse_all(input, |cursor: &mut Cursor| {
+        let stacks = parse_stacks(cursor)?;
+        literal("\n")(cursor)?;
+        let commands = parse_commands(stacks.len())(cursor)?;
+        Ok((stacks, commands))
+    })
+}
+
+/// Returns `None` if a command takes a crate from an empty stack or a stack ends up empty.
+pub fn task_1(mut stacks: Vec<Vec<char>>, commands: &[Command]) -> Option<String> {
+    for command in commands {
+        for _ in 0..command.count {
+            let item = stacks[command.source - 1].pop()?;
+            stacks[command.destination - 1].push(item);
+        }
+    }
+
+    stacks.iter().map(|stack| stack.last()).collect()
+}
+
+/// Returns `None` if a command takes a crate from an empty stack or a stack ends up empty.
+pub fn task_2(mut stacks: Vec<Vec<char>>, commands: &[Command]) -> Option<String> {
+    for command in commands {
+        let mut items = Vec::new();
+        for _ in 0..command.count {
+            items.push(stacks[command.source - 1].pop()?);
+        }
+        stacks[command.destination - 1].extend(items.iter().rev());
+    }
+
+    stacks.iter().map(|stack| stack.last()).collect()
+}
+
+pub struct SupplyStacks {
+    stacks: Vec<Vec<char>>,
+    commands: Vec<Command>,
+}
+
+impl Solution for SupplyStacks {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let (stacks, commands) = parse_input(input)?;
+        Ok(Self { stacks, commands })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        Ok(task_1(self.stacks.clone(), &self.commands)
+            .ok_or_eyre("commands move crates off an empty stack")?
+            .into())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        Ok(task_2(self.stacks.clone(), &self.commands)
+            .ok_or_eyre("commands move crates off an empty stack")?
+            .into())
+    }
+}
+
+/// Generates `size` commands on 9 stacks; real inputs have about 500 commands and 50 crates.
+///
+/// Commands never empty a stack, so every stack has a crate on top in the end.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const STACKS: usize = 9;
+    let crates = (size / 10).max(2 * STACKS);
+
+    let mut stacks = vec![Vec::new(); STACKS];
+    for index in 0..crates {
+        // every stack gets at least one crate
+        let stack = if index < STACKS {
+            index
+        } else {
+            rng.index(STACKS)
+        };
+        stacks[stack].push((b'A' + rng.index(26) as u8) as char);
+    }
+
+    let mut output = String::new();
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    for level in (0..height).rev() {
+        let slots: Vec<String> = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(item) => format!("[{item}]"),
+                None => "   ".to_string(),
+            })
+            .collect();
+        output += &slots.join(" ");
+        output.push('\n');
+    }
+    let numbers: Vec<String> = (1..=STACKS).map(|number| format!(" {number} ")).collect();
+    output += &numbers.join(" ");
+    output += "\n\n";
+
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    for _ in 0..size {
+        let sources: Vec<usize> = (0..STACKS).filter(|stack| heights[*stack] > 1).collect();
+        let source = rng.choose(&sources);
+        let destination = (source + 1 + rng.index(STACKS - 1)) % STACKS;
+        let count = rng.index(heights[source] - 1) + 1;
+        heights[source] -= count;
+        heights[destination] += count;
+        output += &format!("move {count} from {} to {}\n", source + 1, destination + 1);
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn invalid_input() {
+        let input = include_str!("../example");
+
+        let error = SupplyStacks::parse(&input.replace("from 1", "from 9"))
+            .err()
+            .unwrap();
+        assert_eq!(error.line, 7);
+
+        let error = SupplyStacks::parse(&input.replace("[Z]", "(Z)"))
+            .err()
+            .unwrap();
+        assert_eq!((error.line, error.column), (3, 1));
+    }
+
+    #[test]
+    fn generated() {
+        let input = generate(&mut Rng::new(1), 200);
+        let solution = SupplyStacks::parse(&input).unwrap();
+        assert_eq!(solution.commands.len(), 200);
+        assert_eq!(solution.part_1().unwrap().to_string().len(), 9);
+        assert_eq!(solution.part_2().unwrap().to_string().len(), 9);
+    }
+}
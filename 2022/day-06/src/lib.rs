@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+
+use aoc_common::{line, parse_all, take_while, Answer, Cursor, Day, ParseError, Rng, Solution};
+use eyre::{OptionExt, Result};
+
+pub const DAY: Day = Day::new::<TuningTrouble>(6, "Tuning Trouble", env!("CARGO_MANIFEST_DIR"))
+    .with_example(include_str!("../example"))
+    .with_generator(generate);
+
+/// Returns `None` if no window of `window_size` distinct characters exists.
+pub fn start_of_packet(data: &str, window_size: usize) -> Option<usize> {
+    let chars: Vec<_> = data.chars().collect();
+    chars
+        .windows(window_size)
+        .enumerate()
+        .find(|(_index, window)| HashSet::<_>::from_iter(window.iter()).len() == window_size)
+        .map(|(index, _window)| index + window_size)
+}
+
+pub fn parse_datastream<'a>(input: &'a str) -> Result<&'a str, ParseError> {
+    let data = |cursor: &mut Cursor<'a>| {
+        let data = take_while(|next| next.is_ascii_lowercase())(cursor)?;
+        if data.is_empty() || !cursor.is_at_end() {
+            return Err(cursor.expected("a lowercase letter"));
+        }
+        Ok(data)
+    };
+    parse_all(input, line(data))
+}
+
+pub struct TuningTrouble {
+    data: String,
+}
+
+impl Solution for TuningTrouble {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            data: parse_datastream(input)?.to_string(),
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        Ok(start_of_packet(&self.data, 4)
+            .ok_or_eyre("no start-of-packet marker found")?
+            .into())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        Ok(start_of_packet(&self.data, 14)
+            .ok_or_eyre("no start-of-message marker found")?
+            .into())
+    }
+}
+
+/// Generates a datastream of `size` characters; real inputs have 4096.
+///
+/// Most of the stream repeats eight letters, so the start-of-message marker only appears where
+/// fourteen distinct letters were placed in its second half. Streams are at least 28 characters
+/// long, so that half has room for them.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(28);
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    let mut stream: Vec<char> = (0..size).map(|_| rng.choose(&letters[..8])).collect();
+
+    rng.shuffle(&mut letters);
+    let start = size / 2 + rng.index(size - 14 - size / 2 + 1);
+    stream[start..start + 14].copy_from_slice(&letters[..14]);
+    stream.into_iter().chain(Some('\n')).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn invalid_datastream() {
+        let error = parse_datastream("mjqjpq mgbljsp\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 7));
+
+        assert!(parse_datastream("\n").is_err());
+        assert_eq!(start_of_packet("aaaaaaa", 4), None);
+    }
+
+    #[test]
+    fn generated() {
+        let input = generate(&mut Rng::new(1), 1000);
+        let stream = parse_datastream(&input).unwrap();
+        assert_eq!(stream.len(), 1000);
+        assert!(start_of_packet(stream, 4).is_some());
+        assert!(start_of_packet(stream, 14).unwrap() > 500);
+
+        for size in 0..30 {
+            let input = generate(&mut Rng::new(1), size);
+            let solution = TuningTrouble::parse(&input).unwrap();
+            assert_eq!(solution.data.len(), size.max(28));
+            assert!(solution.part_1().is_ok() && solution.part_2().is_ok());
+        }
+    }
+}
@@ -0,0 +1,389 @@
+use std::{
+    collections::{HashMap, HashSet},
+    iter::once,
+};
+
+use aoc_common::{
+    labelled, lines, parse_all, parse_parameter, unsigned, word, Answer, Cursor, Day, Parameter,
+    ParseError, Rng, Solution,
+};
+use eyre::{bail, OptionExt, Result};
+use tracing::{debug, trace};
+
+pub const DAY: Day =
+    Day::new::<NoSpaceLeftOnDevice>(7, "No Space Left On Device", env!("CARGO_MANIFEST_DIR"))
+        .with_example(include_str!("../example"))
+        .with_parameters(&[
+            Parameter::new("disk_size", "70000000", "Total space on the disk"),
+            Parameter::new(
+                "update_size",
+                "30000000",
+                "Free space the update needs in part 2",
+            ),
+        ])
+        .with_generator(generate);
+
+#[derive(Debug)]
+pub enum Item {
+    Directory(Directory),
+    File(File),
+}
+
+impl Item {
+    pub fn file(size: u32) -> Self {
+        Item::File(File { size })
+    }
+
+    pub fn directory() -> Self {
+        Item::Directory(Directory::default())
+    }
+
+    pub fn size(&self) -> u32 {
+        match self {
+            Item::Directory(directory) => directory.size(),
+            Item::File(file) => file.size,
+        }
+    }
+
+    pub fn at_mut(&mut self, path: &[String]) -> Option<&mut Self> {
+        let mut current = self;
+        for element in path {
+            current = match current {
+                Item::Directory(directory) => directory.children.get_mut(element)?,
+                Item::File(_) => return None,
+            }
+        }
+
+        Some(current)
+    }
+
+    /// Returns the item now stored under `name`, or `None` if `path` is not a directory.
+    pub fn insert_at(&mut self, path: &[String], name: String, item: Item) -> Option<&mut Item> {
+        match self.at_mut(path)? {
+            Item::Directory(directory) => Some(directory.children.entry(name).or_insert(item)),
+            Item::File(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Directory {
+    pub children: HashMap<String, Item>,
+}
+
+impl Directory {
+    pub fn size(&self) -> u32 {
+        self.children.values().map(|child| child.size()).sum()
+    }
+}
+
+#[derive(Debug)]
+pub struct File {
+    pub size: u32,
+}
+
+/// How deep directories may nest, so that a hostile transcript cannot overflow the stack.
+///
+/// Sizing and dropping the tree recurse once per level, so this stays well within the 2 MiB
+/// stack of a spawned thread while leaving room above what `generate` writes.
+pub const MAX_DEPTH: usize = 1_000;
+
+/// A line of the terminal transcript.
+enum Output<'a> {
+    ChangeDirectory(&'a str),
+    List,
+    Directory(&'a str),
+    File(u32, &'a str),
+}
+
+fn parse_output<'a>(cursor: &mut Cursor<'a>) -> Result<Output<'a>, ParseError> {
+    if cursor.eat("$ ") {
+        if cursor.eat("ls") {
+            return Ok(Output::List);
+        }
+        if cursor.eat("cd ") {
+            return Ok(Output::ChangeDirectory(word(cursor)?));
+        }
+        return Err(cursor.expected("'cd' or 'ls'"));
+    }
+    if cursor.eat("dir ") {
+        return Ok(Output::Directory(word(cursor)?));
+    }
+    if !cursor
+        .rest()
+        .starts_with(|next: char| next.is_ascii_digit())
+    {
+        return Err(cursor.expected("a command, a directory or a file with its size"));
+    }
+    let size = unsigned(cursor)?;
+    Ok(Output::File(size, labelled(" ", word)(cursor)?))
+}
+
+pub fn generate_tree(input: &str) -> Result<Item, ParseError> {
+    let mut root = Item::directory();
+
+    let mut current_path = Vec::new();
+
+    for output in parse_all(input, lines(parse_output))? {
+        let error = |fragment: &str, message: &str| ParseError::new(input, fragment, message);
+
+        match output {
+            Output::ChangeDirectory("/") => {
+                current_path.clear();
+            }
+            Output::ChangeDirectory("..") => {
+                current_path.pop();
+            }
+            Output::ChangeDirectory(name) => {
+                if current_path.len() == MAX_DEPTH {
+                    return Err(error(
+                        name,
+                        &format!("directories nest more than {MAX_DEPTH} deep"),
+                    ));
+                }
+                match root.insert_at(&current_path, name.to_string(), Item::directory()) {
+                    Some(Item::Directory(_)) => current_path.push(name.to_string()),
+                    _ => return Err(error(name, "cannot cd into a file")),
+                }
+            }
+            Output::List => {}
+            Output::Directory(name) => {
+                root.insert_at(&current_path, name.to_string(), Item::directory());
+            }
+            Output::File(size, name) => {
+                root.insert_at(&current_path, name.to_string(), Item::file(size));
+            }
+        }
+    }
+
+    Ok(root)
+}
+
+pub fn task_1(tree: &Item) -> u32 {
+    match tree {
+        Item::Directory(directory) => {
+            let total = directory.children.values().map(task_1).sum();
+            let size = tree.size();
+            if size < 100_000 {
+                total + size
+            } else {
+                total
+            }
+        }
+        Item::File(_) => 0,
+    }
+}
+
+pub fn task_2(tree: &Item, minimum_size: u32) -> Option<u32> {
+    match tree {
+        Item::Directory(directory) => directory
+            .children
+            .values()
+            .filter_map(|item| task_2(item, minimum_size))
+            .chain(once(tree.size()))
+            .filter(|size| *size > minimum_size)
+            .min(),
+        Item::File(_) => None,
+    }
+}
+
+pub struct NoSpaceLeftOnDevice {
+    tree: Item,
+    disk_size: u32,
+    update_size: u32,
+}
+
+impl Solution for NoSpaceLeftOnDevice {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let tree = generate_tree(input)?;
+        debug!(used_space = tree.size(), "generated file system tree");
+        trace!(?tree);
+        Ok(Self {
+            tree,
+            disk_size: 70_000_000,
+            update_size: 30_000_000,
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        Ok(task_1(&self.tree).into())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        let used_space = self.tree.size();
+        let free_space = self
+            .disk_size
+            .checked_sub(used_space)
+            .ok_or_eyre("files take up more than the whole disk")?;
+        let required_additional_space = self.update_size.saturating_sub(free_space);
+        let size = task_2(&self.tree, required_additional_space)
+            .ok_or_eyre("no suitable directory to delete found")?;
+        Ok(size.into())
+    }
+
+    fn set_parameter(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "disk_size" => self.disk_size = parse_parameter(name, value)?,
+            "update_size" => self.update_size = parse_parameter(name, value)?,
+            _ => bail!("unknown parameter '{name}'"),
+        }
+        Ok(())
+    }
+}
+
+/// Generates a transcript exploring `size` directories; real inputs have about 200.
+///
+/// The first half of the directories, but at most `MAX_DEPTH / 2`, nest in one long chain and
+/// the others go into random directories, so the tree is a little more than `size / 2` levels
+/// deep. The files add up to between 40M and 65M, which leaves something to delete in part 2.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut names = vec![String::new()];
+    let mut children: Vec<Vec<usize>> = vec![Vec::new()];
+    let mut taken: Vec<HashSet<String>> = vec![HashSet::new()];
+    let unique_name = |rng: &mut Rng, taken: &mut HashSet<String>, extension: bool| loop {
+        let mut name: String = (0..rng.range(1..=8))
+            .map(|_| (b'a' + rng.index(26) as u8) as char)
+            .collect();
+        if extension && rng.chance(0.5) {
+            name.push_str(rng.choose(&[".txt", ".dat", ".log", ".lst", ".ext"]));
+        }
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    };
+
+    let chain = (size / 2).min(MAX_DEPTH / 2);
+    for directory in 1..size {
+        let parent = if directory <= chain {
+            directory - 1
+        } else {
+            rng.index(directory)
+        };
+        names.push(unique_name(rng, &mut taken[parent], false));
+        children[parent].push(directory);
+        children.push(Vec::new());
+        taken.push(HashSet::new());
+    }
+
+    let file_count = 2 * size;
+    let used = rng.range(40_000_000..=65_000_000) as u64;
+    let mut files: Vec<Vec<(String, u64)>> = vec![Vec::new(); size];
+    for _ in 0..file_count {
+        let directory = rng.index(size);
+        let name = unique_name(rng, &mut taken[directory], true);
+        let file_size = rng.range(1..=(2 * used / file_count as u64).max(1) as i64) as u64;
+        files[directory].push((name, file_size));
+    }
+
+    // walk the tree without recursion, it can be deeper than the stack allows
+    let mut output = String::from("$ cd /\n");
+    let mut stack = vec![(0, false)];
+    while let Some((directory, done)) = stack.pop() {
+        if done {
+            output += "$ cd ..\n";
+            continue;
+        }
+        if directory != 0 {
+            output += &format!("$ cd {}\n", names[directory]);
+            stack.push((directory, true));
+        }
+        output += "$ ls\n";
+        let mut entries: Vec<String> = children[directory]
+            .iter()
+            .map(|child| format!("dir {}\n", names[*child]))
+            .chain(
+                files[directory]
+                    .iter()
+                    .map(|(name, size)| format!("{size} {name}\n")),
+            )
+            .collect();
+        rng.shuffle(&mut entries);
+        output.extend(entries);
+        stack.extend(
+            children[directory]
+                .iter()
+                .rev()
+                .map(|child| (*child, false)),
+        );
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::read_to_string;
+
+    use super::*;
+
+    #[test]
+    fn example_used_space() {
+        let input = read_to_string("example").unwrap();
+        let tree = generate_tree(&input).unwrap();
+
+        assert_eq!(tree.size(), 48381165);
+    }
+
+    #[test]
+    fn invalid_terminal_output() {
+        let error = generate_tree("$ cd /\n$ ls\n12x b.txt\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 3));
+        let error = generate_tree("$ cd /\nb.txt 12\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = generate_tree("$ ls\n42 a\n$ cd a\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 6));
+
+        let error = generate_tree("$ rm -rf /\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+
+        let error = generate_tree(&"$ cd a\n".repeat(2 * MAX_DEPTH)).unwrap_err();
+        assert_eq!((error.line, error.column), (MAX_DEPTH + 1, 6));
+    }
+
+    #[test]
+    fn generated() {
+        let input = generate(&mut Rng::new(1), 100);
+        let solution = NoSpaceLeftOnDevice::parse(&input).unwrap();
+        assert!((40_000_000..=65_000_000).contains(&solution.tree.size()));
+        assert!(solution.part_2().is_ok());
+    }
+
+    #[test]
+    fn generated_deep() {
+        let input = generate(&mut Rng::new(1), 1500);
+        let solution = NoSpaceLeftOnDevice::parse(&input).unwrap();
+        assert!(solution.part_1().is_ok());
+        assert!(solution.part_2().is_ok());
+
+        let deepest = "$ cd a\n".repeat(MAX_DEPTH) + "$ ls\n1 b\n";
+        let tree = generate_tree(&deepest).unwrap();
+        assert_eq!(
+            (task_1(&tree), task_2(&tree, 0)),
+            (MAX_DEPTH as u32 + 1, Some(1))
+        );
+    }
+
+    #[test]
+    fn generated_depth() {
+        let depth_of = |input: &str| {
+            let mut depth = 0;
+            let mut deepest = 0;
+            for line in input.lines() {
+                match line.strip_prefix("$ cd ") {
+                    Some("/") => depth = 0,
+                    Some("..") => depth -= 1,
+                    Some(_) => depth += 1,
+                    None => {}
+                }
+                deepest = deepest.max(depth);
+            }
+            deepest
+        };
+        for size in [200, 1000, 5000] {
+            let deepest = depth_of(&generate(&mut Rng::new(1), size));
+            let chain = (size / 2).min(MAX_DEPTH / 2);
+            assert!((chain..chain + 50).contains(&deepest), "{size}: {deepest}");
+        }
+    }
+}
@@ -1,168 +1,5 @@
-use std::{collections::HashMap, fs::read_to_string, iter::once};
+use aoc_common::{run, InputSource, Part};
 
-#[derive(Debug)]
-enum Item {
-    Directory(Directory),
-    File(File),
-}
-
-impl Item {
-    fn file(size: u32) -> Self {
-        Item::File(File { size })
-    }
-
-    fn directory() -> Self {
-        Item::Directory(Directory::default())
-    }
-
-    fn size(&self) -> u32 {
-        match self {
-            Item::Directory(directory) => directory.size(),
-            Item::File(file) => file.size,
-        }
-    }
-
-    fn at_mut(&mut self, path: &[String]) -> Option<&mut Self> {
-        let mut current = self;
-        for element in path {
-            current = match current {
-                Item::Directory(directory) => directory.children.get_mut(element)?,
-                Item::File(_) => return None,
-            }
-        }
-
-        Some(current)
-    }
-
-    fn insert_at(&mut self, path: &[String], name: String, item: Item) {
-        let directory = match self.at_mut(path) {
-            Some(Item::Directory(directory)) => directory,
-            Some(Item::File(_)) => panic!("Tried to cd into a file"),
-            None => panic!("requested directory does not exist!"),
-        };
-        directory.children.entry(name).or_insert(item);
-    }
-}
-
-#[derive(Debug, Default)]
-struct Directory {
-    children: HashMap<String, Item>,
-}
-
-impl Directory {
-    fn size(&self) -> u32 {
-        self.children
-            .iter()
-            .map(|(_name, child)| child.size())
-            .sum()
-    }
-}
-
-#[derive(Debug)]
-struct File {
-    size: u32,
-}
-
-fn generate_tree(input: &str) -> Item {
-    let mut root = Item::directory();
-
-    let mut current_path = Vec::new();
-
-    for line in input.lines() {
-        let mut words = line.split_whitespace();
-        match (words.next().unwrap(), words.next().unwrap(), words.next()) {
-            ("$", "cd", Some("/")) => {
-                current_path.clear();
-            }
-            ("$", "cd", Some("..")) => {
-                current_path.pop();
-            }
-            ("$", "cd", Some(name)) => {
-                root.insert_at(&current_path, name.to_string(), Item::directory());
-                current_path.push(name.to_string());
-            }
-            ("$", "ls", None) => {}
-            ("dir", name, None) => {
-                root.insert_at(&current_path, name.to_string(), Item::directory());
-            }
-            (size, name, None) => {
-                root.insert_at(
-                    &current_path,
-                    name.to_string(),
-                    Item::file(size.parse().expect("file size not an integer: {size}")),
-                );
-            }
-            words => panic!("Unknown command: {words:?}"),
-        }
-    }
-
-    root
-}
-
-fn task_1(tree: &Item) -> u32 {
-    match tree {
-        Item::Directory(directory) => {
-            let total = directory
-                .children
-                .iter()
-                .map(|(_name, item)| task_1(item))
-                .sum();
-            let size = tree.size();
-            if size < 100_000 {
-                total + size
-            } else {
-                total
-            }
-        }
-        Item::File(_) => 0,
-    }
-}
-
-fn task_2(tree: &Item, minimum_size: u32) -> Option<u32> {
-    match tree {
-        Item::Directory(directory) => directory
-            .children
-            .iter()
-            .filter_map(|(_name, item)| task_2(item, minimum_size))
-            .chain(once(tree.size()))
-            .filter(|size| *size > minimum_size)
-            .min(),
-        Item::File(_) => None,
-    }
-}
-
-fn main() {
-    let input = read_to_string("input").unwrap();
-    let tree = generate_tree(&input);
-    println!("{:#?}", tree);
-    println!("Task 1: {}", task_1(&tree));
-    let total_space = 70000000;
-    let used_space = tree.size();
-    let free_space = total_space - used_space;
-    let required_additional_space = 30000000 - free_space;
-    println!(
-        "Task 2: {}",
-        task_2(&tree, required_additional_space).expect("no suitable directory to delete found")
-    );
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn example_task_1() {
-        let input = read_to_string("example").unwrap();
-        let tree = generate_tree(&input);
-
-        assert_eq!(tree.size(), 48381165);
-    }
-
-    #[test]
-    fn example_used_space() {
-        let input = read_to_string("example").unwrap();
-        let tree = generate_tree(&input);
-
-        assert_eq!(tree.size(), 48381165);
-    }
+fn main() -> eyre::Result<()> {
+    run(&day_2022_07::DAY, &InputSource::Default, &Part::ALL)
 }
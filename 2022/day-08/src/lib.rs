@@ -0,0 +1,169 @@
+use aoc_common::{Answer, Day, Direction, Grid, ParseError, Point, Rng, Solution};
+use eyre::{OptionExt, Result};
+
+pub const DAY: Day =
+    Day::new::<TreetopTreeHouse>(8, "Treetop Tree House", env!("CARGO_MANIFEST_DIR"))
+        .with_example(include_str!("../example"))
+        .with_generator(generate);
+
+pub struct Map {
+    map: Grid<u32>,
+}
+
+impl Map {
+    pub fn at(&self, tree: Point) -> Option<u32> {
+        self.map.get(tree).copied()
+    }
+
+    /// Heights of the trees from `tree` towards the edge, excluding the tree itself.
+    fn line_of_sight(
+        &self,
+        tree: Point,
+        direction: Direction,
+    ) -> impl Iterator<Item = u32> + Clone + '_ {
+        self.map
+            .ray(tree, direction.vector())
+            .map(|point| self.map[point])
+    }
+
+    pub fn visible(&self, tree: Point) -> Option<bool> {
+        let height = self.at(tree)?;
+        Some(Direction::ALL.iter().any(|direction| {
+            self.line_of_sight(tree, *direction)
+                .max()
+                .map(|max| max < height)
+                .unwrap_or(true)
+        }))
+    }
+
+    pub fn count_visible(&self) -> usize {
+        self.map
+            .points()
+            .filter(|tree| self.visible(*tree).unwrap())
+            .count()
+    }
+
+    pub fn scenic_score(&self, tree: Point) -> Option<usize> {
+        let height = self.at(tree)?;
+        Some(
+            Direction::ALL
+                .iter()
+                .map(|direction| {
+                    let iter = self.line_of_sight(tree, *direction);
+                    let max = iter.clone().max().unwrap_or(0);
+                    let count = iter.take_while(|x| *x < height).count();
+
+                    if max >= height {
+                        count + 1
+                    } else {
+                        count
+                    }
+                })
+                .product(),
+        )
+    }
+
+    pub fn best_scenic_score(&self) -> Option<usize> {
+        self.map
+            .points()
+            .map(|tree| self.scenic_score(tree).unwrap())
+            .max()
+    }
+}
+
+impl TryFrom<&str> for Map {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let map = Grid::parse(value, "a tree height", |character| character.to_digit(10))?;
+        Ok(Map { map })
+    }
+}
+
+pub struct TreetopTreeHouse {
+    map: Map,
+}
+
+impl Solution for TreetopTreeHouse {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            map: Map::try_from(input)?,
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        Ok(self.map.count_visible().into())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        let score = self.map.best_scenic_score().ok_or_eyre("map empty?")?;
+        Ok(score.into())
+    }
+}
+
+/// Generates a forest of `size` by `size` trees; real inputs are 99 by 99.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|_| {
+            let mut row: String = (0..size)
+                .map(|_| char::from_digit(rng.index(10) as u32, 10).expect("a digit"))
+                .collect();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::read_to_string;
+
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = read_to_string("example").unwrap();
+
+        let map = Map::try_from(input.as_str()).unwrap();
+        assert_eq!(map.map.to_string(), input);
+        assert_eq!(map.at(Point::new(2, 2)), Some(3));
+
+        // first row
+        assert_eq!(map.visible(Point::new(1, 1)), Some(true));
+        assert_eq!(map.visible(Point::new(2, 1)), Some(true));
+        assert_eq!(map.visible(Point::new(3, 1)), Some(false));
+
+        // second row
+        assert_eq!(map.visible(Point::new(1, 2)), Some(true));
+        assert_eq!(map.visible(Point::new(2, 2)), Some(false));
+        assert_eq!(map.visible(Point::new(3, 2)), Some(true));
+
+        // third row
+        assert_eq!(map.visible(Point::new(1, 3)), Some(false));
+        assert_eq!(map.visible(Point::new(2, 3)), Some(true));
+        assert_eq!(map.visible(Point::new(3, 3)), Some(false));
+
+        assert_eq!(map.count_visible(), 21);
+
+        assert_eq!(map.scenic_score(Point::new(2, 1)), Some(4));
+        assert_eq!(map.scenic_score(Point::new(2, 3)), Some(8));
+    }
+
+    #[test]
+    fn invalid_map() {
+        let error = Map::try_from("303\n2x5\n").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 2));
+
+        let error = Map::try_from("303\n25\n").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn generated() {
+        let map = Map::try_from(generate(&mut Rng::new(1), 20).as_str()).unwrap();
+        assert_eq!(map.map.width(), 20);
+        // at least the edges are visible
+        assert!(map.count_visible() >= 4 * 19);
+    }
+}
@@ -0,0 +1,187 @@
+use std::{
+    collections::HashSet,
+    iter::{once, repeat_n},
+};
+
+use aoc_common::{
+    labelled, lines, one_of, parse_all, unsigned, Answer, Cursor, Day, Direction, ParseError, Part,
+    Point, Rng, Solution, Variant, Vector,
+};
+use eyre::{bail, Result};
+
+pub const DAY: Day = Day::new::<RopeBridge>(9, "Rope Bridge", env!("CARGO_MANIFEST_DIR"))
+    .with_example(include_str!("../example"))
+    .with_generator(generate);
+
+pub fn parse_step(cursor: &mut Cursor) -> Result<impl Iterator<Item = Vector>, ParseError> {
+    let direction = one_of(&[
+        ("R", Direction::Right),
+        ("L", Direction::Left),
+        ("D", Direction::Down),
+        ("U", Direction::Up),
+    ])(cursor)?;
+    let length = labelled(" ", unsigned)(cursor)?;
+    Ok(repeat_n(direction.vector(), length))
+}
+
+pub fn parse_steps(input: &str) -> Result<Vec<Vector>, ParseError> {
+    Ok(parse_all(input, lines(parse_step))?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+pub fn apply_head_steps(steps: &[Vector]) -> Vec<Point> {
+    once(Point::ORIGIN)
+        .chain(steps.iter().scan(Point::ORIGIN, |position, step| {
+            *position += *step;
+            Some(*position)
+        }))
+        .collect()
+}
+
+/// Moves the tail one step towards the head whenever they stop touching.
+pub fn chase_head(positions: &[Point]) -> Vec<Point> {
+    once(Point::ORIGIN)
+        .chain(positions.iter().scan(Point::ORIGIN, |tail, head| {
+            if head.chebyshev(*tail) > 1 {
+                *tail += (*head - *tail).signum();
+            }
+            Some(*tail)
+        }))
+        .collect()
+}
+
+/// Moves every knot of the rope after each head step, and returns the positions of the tail.
+pub fn simulate_rope(steps: &[Vector], knots: usize) -> Vec<Point> {
+    let mut rope = vec![Point::ORIGIN; knots];
+    once(Point::ORIGIN)
+        .chain(steps.iter().map(|step| {
+            rope[0] += *step;
+            for index in 1..knots {
+                let (head, tail) = (rope[index - 1], rope[index]);
+                if head.chebyshev(tail) > 1 {
+                    rope[index] += (head - tail).signum();
+                }
+            }
+            rope[knots - 1]
+        }))
+        .collect()
+}
+
+pub fn count_unique(positions: &[Point]) -> usize {
+    positions.iter().collect::<HashSet<&Point>>().len()
+}
+
+pub struct RopeBridge {
+    steps: Vec<Vector>,
+}
+
+impl Solution for RopeBridge {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            steps: parse_steps(input)?,
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        let positions = apply_head_steps(&self.steps);
+        let tail_positions = chase_head(&positions);
+        Ok(count_unique(&tail_positions).into())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        let mut positions = apply_head_steps(&self.steps);
+        for _ in 0..9 {
+            positions = chase_head(&positions);
+        }
+        Ok(count_unique(&positions).into())
+    }
+
+    fn variants(&self) -> &'static [Variant] {
+        &[Variant {
+            name: "whole rope",
+            part: Part::Two,
+        }]
+    }
+
+    fn solve_variant(&self, name: &str) -> Result<Answer> {
+        match name {
+            "whole rope" => Ok(count_unique(&simulate_rope(&self.steps, 10)).into()),
+            _ => bail!("unknown variant '{name}'"),
+        }
+    }
+}
+
+/// Generates `size` motions of the head; real inputs have 2000.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let direction = rng.choose(&['R', 'L', 'D', 'U']);
+            format!("{direction} {}\n", rng.range(1..=19))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::check_property;
+
+    use super::*;
+
+    #[test]
+    fn two_knots_chase_the_head() {
+        for example in [include_str!("../example"), include_str!("../example2")] {
+            let steps = parse_steps(example).unwrap();
+            let tail = chase_head(&apply_head_steps(&steps));
+            assert_eq!(simulate_rope(&steps, 2), tail[1..]);
+        }
+    }
+
+    #[test]
+    fn invalid_steps() {
+        let error = parse_steps("R 4\nX 2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = parse_steps("R 4\nU -2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn generated() {
+        let input = generate(&mut Rng::new(1), 100);
+        let solution = RopeBridge::parse(&input).unwrap();
+        let tail = solution
+            .part_1()
+            .unwrap()
+            .to_string()
+            .parse::<usize>()
+            .unwrap();
+        let last_knot = solution
+            .part_2()
+            .unwrap()
+            .to_string()
+            .parse::<usize>()
+            .unwrap();
+        assert!((1..=solution.steps.len()).contains(&tail));
+        assert!(last_knot <= tail);
+    }
+
+    #[test]
+    fn strategies_agree() {
+        check_property(100, 200, |rng, size| {
+            let steps = parse_steps(&generate(rng, size)).unwrap();
+            let mut positions = apply_head_steps(&steps);
+            for knots in 2..=10 {
+                positions = chase_head(&positions);
+                // every call to chase_head starts with one more position at the origin
+                let tail_positions = &positions[knots - 1..];
+                assert_eq!(
+                    simulate_rope(&steps, knots),
+                    tail_positions,
+                    "{knots} knots"
+                );
+            }
+        });
+    }
+}
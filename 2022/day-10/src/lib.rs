@@ -0,0 +1,247 @@
+use std::str::FromStr;
+
+use aoc_common::{
+    labelled, lines, parse_all, parse_parameter, signed, Answer, Cursor, Day, Parameter,
+    ParseError, Rng, Solution,
+};
+use eyre::{bail, eyre, Result};
+use tracing::trace;
+
+pub const DAY: Day = Day::new::<CathodeRayTube>(10, "Cathode-Ray Tube", env!("CARGO_MANIFEST_DIR"))
+    .with_example(include_str!("../example"))
+    .with_parameters(&[
+        Parameter::new(
+            "sample_cycles",
+            "20,60,100,140,180,220",
+            "Comma-separated cycles whose signal strengths part 1 adds up",
+        ),
+        Parameter::new(
+            "crt_width",
+            "40",
+            "Pixels in each row of the screen in part 2",
+        ),
+    ])
+    .with_generator(generate);
+
+/// The cycles whose signal strengths the puzzle adds up.
+pub const SAMPLE_CYCLES: [usize; 6] = [20, 60, 100, 140, 180, 220];
+
+pub const CRT_WIDTH: usize = 40;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Instruction {
+    Noop,
+    AddX(i32),
+}
+
+pub fn parse_instruction(cursor: &mut Cursor) -> Result<Instruction, ParseError> {
+    if cursor.eat("noop") {
+        return Ok(Instruction::Noop);
+    }
+    if cursor.eat("addx") {
+        return Ok(Instruction::AddX(labelled(" ", signed)(cursor)?));
+    }
+    Err(cursor.expected("'noop' or 'addx'"))
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse_all(text, parse_instruction)
+    }
+}
+
+pub fn run(instructions: impl Iterator<Item = Instruction>) -> Vec<i32> {
+    let mut outputs = Vec::new();
+    outputs.push(0); // dummy value: "during the 0th cycle"
+
+    let mut x = 1;
+    for instruction in instructions {
+        outputs.push(x);
+        if let Instruction::AddX(value) = instruction {
+            outputs.push(x);
+            x += value
+        }
+    }
+
+    outputs
+}
+
+pub fn signal_strengths(values: &[i32]) -> Vec<i32> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let strength = (index as i32) * value;
+            trace!(cycle = index, x = value, strength);
+            strength
+        })
+        .collect()
+}
+
+/// Returns `None` if the program finishes before the last interesting cycle.
+pub fn task_1(values: &[i32], cycles: &[usize]) -> Option<i32> {
+    cycles.iter().map(|cycle| values.get(*cycle)).sum()
+}
+
+/// Draws the screen with rows of `width` pixels, which must not be 0.
+pub fn task_2(values: &[i32], width: usize) -> Vec<String> {
+    values[1..] // skip dummy value
+        .chunks(width)
+        .map(|line| {
+            line.iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    if (index as i32 - value).abs() < 2 {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub struct CathodeRayTube {
+    instructions: Vec<Instruction>,
+    sample_cycles: Vec<usize>,
+    crt_width: usize,
+}
+
+impl Solution for CathodeRayTube {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            instructions: parse_all(input, lines(parse_instruction))?,
+            sample_cycles: SAMPLE_CYCLES.to_vec(),
+            crt_width: CRT_WIDTH,
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        let outputs = run(self.instructions.iter().copied());
+        let signal_strengths = signal_strengths(&outputs);
+        let sum = task_1(&signal_strengths, &self.sample_cycles).ok_or_else(|| {
+            let last = self.sample_cycles.iter().max().unwrap_or(&0);
+            eyre!("program finishes before cycle {last}")
+        })?;
+        Ok(sum.into())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        let outputs = run(self.instructions.iter().copied());
+        Ok(task_2(&outputs, self.crt_width).into())
+    }
+
+    fn set_parameter(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "sample_cycles" => {
+                self.sample_cycles = value
+                    .split(',')
+                    .map(|cycle| parse_parameter(name, cycle.trim()))
+                    .collect::<Result<_>>()?;
+            }
+            "crt_width" => {
+                self.crt_width = parse_parameter(name, value)?;
+                if self.crt_width == 0 {
+                    bail!("the screen must be at least 1 pixel wide");
+                }
+            }
+            _ => bail!("unknown parameter '{name}'"),
+        }
+        Ok(())
+    }
+}
+
+/// Generates a program of `size` instructions, at least enough for the 240 cycles of the
+/// screen; real inputs have about 140.
+///
+/// Additions keep the sprite on the screen, between -1 and 40.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    let mut x = 1;
+    let mut cycles = 0;
+    let mut instructions = 0;
+    while instructions < size || cycles < 240 {
+        if rng.chance(0.3) {
+            output += "noop\n";
+            cycles += 1;
+        } else {
+            let value = rng.range((-1 - x).max(-20)..=(40 - x).min(20));
+            output += &format!("addx {value}\n");
+            x += value;
+            cycles += 2;
+        }
+        instructions += 1;
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::read_to_string;
+
+    use super::*;
+
+    #[test]
+    fn example_task1() {
+        let input = read_to_string("example").unwrap();
+        let instructions = input.lines().map(|line| line.parse().unwrap());
+        let outputs = run(instructions);
+        assert_eq!(outputs[1], 1);
+        assert_eq!(outputs[2], 1);
+        assert_eq!(outputs[3], 16);
+        assert_eq!(outputs[4], 16);
+        assert_eq!(outputs[5], 5);
+
+        assert_eq!(outputs[20], 21);
+
+        let signal_strengths = signal_strengths(&outputs);
+
+        assert_eq!(signal_strengths[20], 420);
+        assert_eq!(signal_strengths[60], 1140);
+        assert_eq!(signal_strengths[100], 1800);
+        assert_eq!(signal_strengths[140], 2940);
+        assert_eq!(signal_strengths[180], 2880);
+        assert_eq!(signal_strengths[220], 3960);
+
+        assert_eq!(task_1(&signal_strengths, &SAMPLE_CYCLES), Some(13140));
+    }
+
+    #[test]
+    fn example_task2() {
+        let input = read_to_string("example").unwrap();
+        let instructions = input.lines().map(|line| line.parse().unwrap());
+        let outputs = run(instructions);
+        let lines = task_2(&outputs, CRT_WIDTH);
+
+        assert_eq!(lines[0], "##..##..##..##..##..##..##..##..##..##..");
+        assert_eq!(lines[1], "###...###...###...###...###...###...###.");
+        assert_eq!(lines[2], "####....####....####....####....####....");
+        assert_eq!(lines[3], "#####.....#####.....#####.....#####.....");
+        assert_eq!(lines[4], "######......######......######......####");
+        assert_eq!(lines[5], "#######.......#######.......#######.....");
+    }
+
+    #[test]
+    fn invalid_instructions() {
+        let error = "addx 1 2".parse::<Instruction>().unwrap_err();
+        assert_eq!(error.column, 8);
+
+        let error = parse_all("noop\naddx\n", lines(parse_instruction)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+
+        let error = "mul 3".parse::<Instruction>().unwrap_err();
+        assert_eq!(error.column, 1);
+    }
+
+    #[test]
+    fn generated() {
+        let input = generate(&mut Rng::new(1), 100);
+        let instructions = parse_all(&input, lines(parse_instruction)).unwrap();
+        let outputs = run(instructions.into_iter());
+        assert!(task_1(&signal_strengths(&outputs), &SAMPLE_CYCLES).is_some());
+        assert!(task_2(&outputs, CRT_WIDTH).len() >= 6);
+    }
+}
@@ -0,0 +1,335 @@
+use std::str::FromStr;
+
+use aoc_common::{
+    blocks, labelled, line, literal, one_of, parse_all, parse_parameter, separated, unsigned,
+    Answer, Cursor, Day, Parameter, ParseError, Rng, Solution,
+};
+use eyre::{bail, OptionExt, Result};
+
+pub const DAY: Day =
+    Day::new::<MonkeyInTheMiddle>(11, "Monkey in the Middle", env!("CARGO_MANIFEST_DIR"))
+        .with_example(include_str!("../example"))
+        .with_parameters(&[
+            Parameter::new("part_1_rounds", "20", "Rounds the monkeys play in part 1"),
+            Parameter::new(
+                "part_2_rounds",
+                "10000",
+                "Rounds the monkeys play in part 2",
+            ),
+            Parameter::new(
+                "worry_divisor",
+                "3",
+                "How much relief divides the worry level after each inspection in part 1",
+            ),
+        ])
+        .with_generator(generate);
+
+#[derive(Debug, Clone, Copy)]
+pub enum Operation {
+    Add(u64),
+    Multiply(u64),
+    Square,
+    Double,
+}
+
+impl Operation {
+    /// Returns `None` if the worry level overflows.
+    pub fn apply(&self, old: u64) -> Option<u64> {
+        match self {
+            Operation::Add(value) => old.checked_add(*value),
+            Operation::Multiply(value) => old.checked_mul(*value),
+            Operation::Square => old.checked_mul(old),
+            Operation::Double => old.checked_add(old),
+        }
+    }
+}
+
+/// Parses the right-hand side of an operation, like `old * 19` or `old + old`.
+pub fn parse_operation(cursor: &mut Cursor) -> Result<Operation, ParseError> {
+    literal("old ")(cursor)?;
+    let add = one_of(&[("+ ", true), ("* ", false)])(cursor)?;
+    let operand = if cursor.eat("old") {
+        None
+    } else {
+        Some(unsigned(cursor)?)
+    };
+    Ok(match (add, operand) {
+        (true, None) => Operation::Double,
+        (true, Some(value)) => Operation::Add(value),
+        (false, None) => Operation::Square,
+        (false, Some(value)) => Operation::Multiply(value),
+    })
+}
+
+impl FromStr for Operation {
+    type Err = ParseError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        parse_all(expression, parse_operation)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    pub items: Vec<u64>,
+    pub operation: Operation,
+    pub test_divisor: u64,
+    pub test_targets_true: usize,
+    pub test_targets_false: usize,
+}
+
+impl Monkey {
+    pub fn inspect(&self, item: u64) -> Option<u64> {
+        self.operation.apply(item)
+    }
+    pub fn throw(&self, item: u64) -> usize {
+        if item.is_multiple_of(self.test_divisor) {
+            self.test_targets_true
+        } else {
+            self.test_targets_false
+        }
+    }
+}
+
+impl Monkey {
+    /// Parses one block of the input, along with where its targets are, as they can only be
+    /// checked once all monkeys are known.
+    fn parse<'a>(cursor: &mut Cursor<'a>) -> Result<(Self, [Cursor<'a>; 2]), ParseError> {
+        line(labelled("Monkey ", |cursor: &mut Cursor<'a>| {
+            unsigned::<usize>(cursor)?;
+            literal(":")(cursor)
+        }))(cursor)?;
+        let items = line(labelled("  Starting items: ", separated(unsigned, ", ")))(cursor)?;
+        let operation = line(labelled("  Operation: new = ", parse_operation))(cursor)?;
+
+        literal("  Test: divisible by ")(cursor)?;
+        let divisor_at = *cursor;
+        let test_divisor = line(unsigned)(cursor)?;
+        if test_divisor == 0 {
+            return Err(divisor_at.error("cannot test divisibility by 0"));
+        }
+
+        literal("    If true: throw to monkey ")(cursor)?;
+        let true_at = *cursor;
+        let test_targets_true = line(unsigned)(cursor)?;
+        literal("    If false: throw to monkey ")(cursor)?;
+        let false_at = *cursor;
+        let test_targets_false = line(unsigned)(cursor)?;
+
+        let monkey = Self {
+            items,
+            operation,
+            test_divisor,
+            test_targets_true,
+            test_targets_false,
+        };
+        Ok((monkey, [true_at, false_at]))
+    }
+}
+
+pub fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let monkeys = parse_all(input, blocks(Monkey::parse))?;
+    if monkeys.len() < 2 {
+        return Err(ParseError::end_of(input, "expected at least two monkeys"));
+    }
+    let monkey_count = monkeys.len();
+    monkeys
+        .into_iter()
+        .map(|(monkey, targets_at)| {
+            let targets = [monkey.test_targets_true, monkey.test_targets_false];
+            for (index, at) in targets.into_iter().zip(targets_at) {
+                if index >= monkey_count {
+                    return Err(at.error(format!(
+                        "monkey {index} does not exist, there are {monkey_count} monkeys"
+                    )));
+                }
+            }
+            Ok(monkey)
+        })
+        .collect()
+}
+
+/// Plays a round where `relief` adjusts the worry level after every inspection.
+///
+/// Returns `None` if a worry level overflows.
+pub fn play_round(monkeys: &mut [Monkey], relief: impl Fn(u64) -> u64) -> Option<Vec<usize>> {
+    let mut business: Vec<usize> = vec![0; monkeys.len()];
+
+    for index in 0..monkeys.len() {
+        let items: Vec<u64> = monkeys[index].items.drain(..).collect();
+        business[index] += items.len();
+
+        for item in items {
+            let new_item = relief(monkeys[index].inspect(item)?);
+            let new_index = monkeys[index].throw(new_item);
+            monkeys[new_index].items.push(new_item);
+        }
+    }
+
+    Some(business)
+}
+
+/// Plays `rounds` rounds, dividing worry levels by `worry_divisor`, which must not be 0.
+///
+/// Returns `None` if a worry level overflows, which a small divisor allows.
+pub fn task_1(monkeys: &mut [Monkey], rounds: usize, worry_divisor: u64) -> Option<usize> {
+    let mut business: Vec<usize> = vec![0; monkeys.len()];
+
+    for _round in 0..rounds {
+        let new_busines = play_round(monkeys, |worry| worry / worry_divisor)?;
+        business
+            .iter_mut()
+            .zip(new_busines)
+            .for_each(|(old, additional)| *old += additional)
+    }
+
+    business.sort();
+    business.reverse();
+    Some(business[0] * business[1])
+}
+
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Returns `None` if a worry level overflows even below the common multiple of the tests.
+pub fn task_2(monkeys: &mut [Monkey], rounds: usize) -> Option<usize> {
+    let mut business: Vec<usize> = vec![0; monkeys.len()];
+
+    // the least common multiple keeps every test intact and stays small for repeated divisors
+    let divisor = monkeys
+        .iter()
+        .map(|monkey| monkey.test_divisor)
+        .fold(1, |lcm, divisor| lcm / gcd(lcm, divisor) * divisor);
+
+    for _round in 0..rounds {
+        let new_busines = play_round(monkeys, |worry| worry % divisor)?;
+        business
+            .iter_mut()
+            .zip(new_busines)
+            .for_each(|(old, additional)| *old += additional);
+    }
+
+    business.sort();
+    business.reverse();
+    Some(business[0] * business[1])
+}
+
+pub struct MonkeyInTheMiddle {
+    monkeys: Vec<Monkey>,
+    part_1_rounds: usize,
+    part_2_rounds: usize,
+    worry_divisor: u64,
+}
+
+impl Solution for MonkeyInTheMiddle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            monkeys: parse_monkeys(input)?,
+            part_1_rounds: 20,
+            part_2_rounds: 10_000,
+            worry_divisor: 3,
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        let mut monkeys = self.monkeys.clone();
+        let business = task_1(&mut monkeys, self.part_1_rounds, self.worry_divisor)
+            .ok_or_eyre("worry levels overflow")?;
+        Ok(business.into())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        let business = task_2(&mut self.monkeys.clone(), self.part_2_rounds)
+            .ok_or_eyre("worry levels overflow")?;
+        Ok(business.into())
+    }
+
+    fn set_parameter(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "part_1_rounds" => self.part_1_rounds = parse_parameter(name, value)?,
+            "part_2_rounds" => self.part_2_rounds = parse_parameter(name, value)?,
+            "worry_divisor" => {
+                self.worry_divisor = parse_parameter(name, value)?;
+                if self.worry_divisor == 0 {
+                    bail!("the worry divisor must be at least 1");
+                }
+            }
+            _ => bail!("unknown parameter '{name}'"),
+        }
+        Ok(())
+    }
+}
+
+/// Generates `size` monkeys, at least three; real inputs have 8.
+///
+/// Like in real inputs, one monkey squares the worry level, a quarter multiply it and the rest
+/// add to it. Nobody throws to the squaring monkey, otherwise worry levels overflow in part 1
+/// within a few rounds. The divisors are small primes, so part 2 can keep worry levels below their
+/// least common multiple however many monkeys there are.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const PRIMES: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    let size = size.max(3);
+    let squaring = rng.index(size);
+
+    let monkeys: Vec<String> = (0..size)
+        .map(|index| {
+            let items: Vec<String> = (0..rng.range(1..=8))
+                .map(|_| rng.range(50..=99).to_string())
+                .collect();
+            let operation = if index == squaring {
+                "old * old".to_string()
+            } else if rng.chance(0.25) {
+                format!("old * {}", rng.range(2..=19))
+            } else {
+                format!("old + {}", rng.range(1..=8))
+            };
+            let targets: Vec<usize> = (0..size)
+                .filter(|target| *target != index && *target != squaring)
+                .collect();
+            let on_true = rng.choose(&targets);
+            let on_false = rng.choose(&targets);
+            format!(
+                "Monkey {index}:\n  Starting items: {}\n  Operation: new = {operation}\n  \
+                 Test: divisible by {}\n    If true: throw to monkey {on_true}\n    \
+                 If false: throw to monkey {on_false}\n",
+                items.join(", "),
+                rng.choose(&PRIMES),
+            )
+        })
+        .collect();
+    monkeys.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::read_to_string;
+
+    use super::*;
+
+    #[test]
+    fn invalid_monkeys() {
+        let input = read_to_string("example").unwrap();
+
+        let error = parse_monkeys(&input.replace("old * 19", "old / 19")).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 24));
+
+        let error = parse_monkeys(&input.replace("monkey 3", "monkey 4")).unwrap_err();
+        assert_eq!((error.line, error.column), (6, 31));
+
+        let error = parse_monkeys(&input.replace("79, 98", "79, x")).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 23));
+    }
+
+    #[test]
+    fn generated() {
+        let input = generate(&mut Rng::new(1), 20);
+        let monkeys = parse_monkeys(&input).unwrap();
+        assert_eq!(monkeys.len(), 20);
+        assert!(task_2(&mut monkeys.clone(), 10_000).unwrap() > 0);
+    }
+}
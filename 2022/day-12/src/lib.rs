@@ -0,0 +1,280 @@
+use std::{collections::VecDeque, fmt::Display, ops::Range};
+
+use aoc_common::{Answer, Day, Direction, Grid, ParseError, Part, Point, Recorder, Rng, Solution};
+use eyre::{OptionExt, Result};
+
+pub const DAY: Day =
+    Day::new::<HillClimbingAlgorithm>(12, "Hill Climbing Algorithm", env!("CARGO_MANIFEST_DIR"))
+        .with_example(include_str!("../example"))
+        .with_generator(generate);
+
+#[derive(Clone)]
+pub struct Cell {
+    symbol: char,
+    cost: usize,
+    best_direction: Option<Direction>,
+}
+
+impl From<char> for Cell {
+    fn from(symbol: char) -> Self {
+        Self {
+            symbol,
+            cost: usize::MAX,
+            best_direction: None,
+        }
+    }
+}
+
+impl Cell {
+    pub fn elevation(&self) -> u8 {
+        (match self.symbol {
+            'S' => b'a',
+            'E' => b'z',
+            c => c as u8,
+        }) - b'a'
+    }
+
+    pub fn is_start(&self) -> bool {
+        self.symbol == 'S'
+    }
+
+    pub fn is_end(&self) -> bool {
+        self.symbol == 'E'
+    }
+
+    pub fn new_symbol(&self) -> char {
+        match self.best_direction {
+            Some(Direction::Up) => '^',
+            Some(Direction::Down) => 'V',
+            Some(Direction::Left) => '<',
+            Some(Direction::Right) => '>',
+            None => self.symbol,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Map {
+    cells: Grid<Cell>,
+}
+
+impl TryFrom<&str> for Map {
+    type Error = ParseError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let cells = Grid::parse(input, "a lowercase letter, 'S' or 'E'", |symbol| {
+            matches!(symbol, 'a'..='z' | 'S' | 'E').then(|| Cell::from(symbol))
+        })?;
+
+        for (symbol, name) in [('S', "start"), ('E', "end")] {
+            let count = cells
+                .iter()
+                .filter(|(_, cell)| cell.symbol == symbol)
+                .count();
+            if count != 1 {
+                return Err(ParseError::end_of(
+                    input,
+                    format!("expected exactly one {name} '{symbol}', found {count}"),
+                ));
+            }
+        }
+
+        Ok(Self { cells })
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.cells.display(|_, cell| cell.new_symbol()).fmt(f)
+    }
+}
+
+impl Map {
+    pub fn apply_direction(&self, current: Point, direction: Direction) -> Option<Point> {
+        let next = current + direction.vector();
+        self.cells.contains(next).then_some(next)
+    }
+
+    /// Finds the cheapest way down from the end to every cell, recording one frame per step.
+    pub fn solve(&mut self, recorder: &mut Recorder) {
+        let mut queue = VecDeque::new();
+
+        let end = self.cells.iter().find(|(_, cell)| cell.is_end()).unwrap().0;
+        queue.push_back(end);
+        self.cells[end].cost = 0;
+        recorder.push(&*self);
+
+        while let Some(index) = queue.pop_front() {
+            let layer_done = queue
+                .front()
+                .is_none_or(|next| self.cells[*next].cost > self.cells[index].cost);
+            for direction in Direction::ALL {
+                if Some(direction) == self.cells[index].best_direction {
+                    continue;
+                }
+                if let Some(neighbor) = self.apply_direction(index, direction) {
+                    if self.cells[neighbor].cost <= self.cells[index].cost + 1 {
+                        continue;
+                    }
+
+                    if self.cells[neighbor].elevation() + 1 < self.cells[index].elevation() {
+                        continue;
+                    }
+                    self.cells[neighbor].cost = self.cells[index].cost + 1;
+                    self.cells[neighbor].best_direction = Some(direction.reverse());
+                    queue.push_back(neighbor);
+                }
+            }
+            if layer_done {
+                recorder.push(&*self);
+            }
+        }
+        recorder.finish(&*self);
+    }
+
+    /// Returns `None` if the summit cannot be reached from the start.
+    pub fn count_steps_to_end(&self) -> Option<usize> {
+        let start = self
+            .cells
+            .iter()
+            .find(|(_, cell)| cell.is_start())
+            .unwrap()
+            .0;
+        Some(self.cells[start].cost).filter(|cost| *cost != usize::MAX)
+    }
+
+    /// Returns `None` if the summit cannot be reached from any square at the lowest elevation.
+    pub fn lowest_starting_cost(&self) -> Option<usize> {
+        self.cells
+            .iter()
+            .map(|(_, cell)| cell)
+            .filter(|cell| cell.elevation() == 0 && cell.cost != usize::MAX)
+            .map(|cell| cell.cost)
+            .min()
+    }
+}
+
+pub struct HillClimbingAlgorithm {
+    map: Map,
+}
+
+impl Solution for HillClimbingAlgorithm {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            map: Map::try_from(input)?,
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        self.visualize(Part::One, &mut Recorder::disabled())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        self.visualize(Part::Two, &mut Recorder::disabled())
+    }
+
+    fn visualize(&self, part: Part, recorder: &mut Recorder) -> Result<Answer> {
+        let mut map = self.map.clone();
+        map.solve(recorder);
+        let steps = match part {
+            Part::One => map.count_steps_to_end(),
+            Part::Two => map.lowest_starting_cost(),
+        };
+        Ok(steps.ok_or_eyre("the summit cannot be reached")?.into())
+    }
+}
+
+/// Generates a heightmap of `size` rows, at least 13, and four times as many columns; real
+/// inputs have 41 rows.
+///
+/// Hills are cones around random peaks, so neighbouring squares differ by at most one level
+/// and every square can reach the summit 'E'. The start sits at the bottom of a pit on the
+/// left, far enough from the summit on the right for the pit not to cut into it.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let height = size.max(13) as i64;
+    let width = 4 * height;
+    let point = |rng: &mut Rng, columns: Range<i64>| {
+        Point::new(
+            rng.range(columns.start..=columns.end - 1),
+            rng.range(0..=height - 1),
+        )
+    };
+    let start = point(rng, 0..width / 4);
+    let summit = point(rng, width * 3 / 4..width);
+    let mut peaks = vec![(summit, 25)];
+    for _ in 0..height / 2 {
+        peaks.push((point(rng, 0..width), rng.range(5..=30)));
+    }
+
+    let mut output = String::new();
+    for y in 0..height {
+        for x in 0..width {
+            let position = Point::new(x, y);
+            let elevation = peaks
+                .iter()
+                .map(|(peak, top)| top - peak.manhattan(position))
+                .max()
+                .unwrap_or(0)
+                .min(start.manhattan(position))
+                .clamp(0, 25);
+            output.push(match position {
+                _ if position == summit => 'E',
+                _ if position == start => 'S',
+                _ => (b'a' + elevation as u8) as char,
+            });
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::read_to_string;
+
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = read_to_string("example").unwrap();
+        let mut map = Map::try_from(input.as_str()).unwrap();
+        assert_eq!(map.to_string(), input);
+        map.solve(&mut Recorder::disabled());
+        assert_eq!(map.count_steps_to_end(), Some(31));
+    }
+
+    #[test]
+    fn unreachable_summit() {
+        let solution = HillClimbingAlgorithm::parse("Sbc\nazE\n").unwrap();
+        assert!(solution.part_1().is_err());
+        assert!(solution.part_2().is_err());
+
+        let solution = HillClimbingAlgorithm::parse(
+            "abcdefghijklmnopqrstuvwxyE\nSzzzzzzzzzzzzzzzzzzzzzzzzz\n",
+        )
+        .unwrap();
+        assert_eq!(solution.part_1().unwrap(), Answer::from(26));
+        assert_eq!(solution.part_2().unwrap(), Answer::from(25));
+    }
+
+    #[test]
+    fn invalid_map() {
+        let error = Map::try_from("Sab\nc1E\n").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 2));
+
+        let error = Map::try_from("Sab\ncE\n").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = Map::try_from("Sab\ncdE\nSab\n").err().unwrap();
+        assert_eq!(error.message, "expected exactly one start 'S', found 2");
+    }
+
+    #[test]
+    fn generated() {
+        let input = generate(&mut Rng::new(1), 20);
+        let mut map = Map::try_from(input.as_str()).unwrap();
+        map.solve(&mut Recorder::disabled());
+        let steps = map.count_steps_to_end().unwrap();
+        assert!(map.lowest_starting_cost().unwrap() <= steps);
+    }
+}
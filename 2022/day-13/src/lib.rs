@@ -0,0 +1,375 @@
+use std::fmt::Display;
+use std::{cmp::Ordering, fmt::Write, str::FromStr};
+
+use aoc_common::{
+    blocks, lines, literal, parse_all, separated, unsigned, Answer, Cursor, Day, ParseError, Rng,
+    Solution,
+};
+use eyre::Result;
+use tracing::{debug, trace};
+
+pub const DAY: Day = Day::new::<DistressSignal>(13, "Distress Signal", env!("CARGO_MANIFEST_DIR"))
+    .with_example(include_str!("../example"))
+    .with_generator(generate);
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Item {
+    Value(u32),
+    List(Vec<Item>),
+}
+
+/// How deep lists may nest, so that a hostile packet cannot overflow the stack.
+///
+/// Parsing, comparing and dropping a packet all recurse once per level, so this stays well
+/// within the 2 MiB stack of a spawned thread while leaving room above what `generate` writes.
+pub const MAX_DEPTH: usize = 1_000;
+
+impl Item {
+    /// Parses a packet, a number or a list like `[1,[2,3]]`.
+    pub fn parse(cursor: &mut Cursor) -> Result<Item, ParseError> {
+        Item::parse_nested(cursor, 0)
+    }
+
+    fn parse_nested(cursor: &mut Cursor, depth: usize) -> Result<Item, ParseError> {
+        if cursor.rest().starts_with('[') {
+            Item::parse_list(cursor, depth)
+        } else if cursor
+            .rest()
+            .starts_with(|next: char| next.is_ascii_digit())
+        {
+            Ok(Item::Value(unsigned(cursor)?))
+        } else {
+            Err(cursor.expected("a list or a number"))
+        }
+    }
+
+    pub fn parse_list(cursor: &mut Cursor, depth: usize) -> Result<Item, ParseError> {
+        if depth == MAX_DEPTH {
+            return Err(cursor.error(format!("lists nest more than {MAX_DEPTH} deep")));
+        }
+        literal("[")(cursor)?;
+        if cursor.eat("]") {
+            return Ok(Item::List(Vec::new()));
+        }
+        let items = separated(
+            |cursor: &mut Cursor| Item::parse_nested(cursor, depth + 1),
+            ",",
+        )(cursor)?;
+        if !cursor.eat("]") {
+            return Err(cursor.expected("',' or ']'"));
+        }
+
+        Ok(Item::List(items))
+    }
+
+    pub fn compare_list(&self, other_item: &Item) -> Ordering {
+        let other = match other_item {
+            Item::List(other) => other,
+            _ => panic!("non list item passed to compare_list"),
+        };
+        let result = match self {
+            Item::Value(value) => Item::List(vec![Item::Value(*value)]).compare_list(other_item),
+            Item::List(list) => list
+                .iter()
+                .zip(other)
+                .map(|(a, b)| a.partial_cmp(b).unwrap())
+                .find(|ordering| *ordering != Ordering::Equal)
+                .unwrap_or_else(|| list.len().partial_cmp(&other.len()).unwrap()),
+        };
+        trace!(left = %self, right = %other_item, ?result, "compared lists");
+        result
+    }
+}
+
+impl Display for Item {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Item::Value(value) => f.write_fmt(format_args!("{value}"))?,
+            Item::List(list) => {
+                f.write_char('[')?;
+                list.iter().enumerate().for_each(|(index, item)| {
+                    if index > 0 {
+                        f.write_char(',').unwrap();
+                    }
+                    f.write_fmt(format_args!("{}", item)).unwrap();
+                });
+                f.write_char(']')?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Item {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse_all(text, Item::parse)
+    }
+}
+
+impl PartialOrd for Item {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Item {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Item::Value(value), Item::Value(value2)) => value.cmp(value2),
+            (Item::Value(_), Item::List(_)) => self.compare_list(other),
+            (Item::List(_), Item::Value(_)) => other.compare_list(self).reverse(),
+            (Item::List(_), Item::List(_)) => self.compare_list(other),
+        }
+    }
+}
+
+pub fn parse_pairs(input: &str) -> Result<Vec<Vec<Item>>, ParseError> {
+    let pair = |cursor: &mut Cursor| {
+        let start = *cursor;
+        let packets = lines(Item::parse)(cursor)?;
+        if packets.len() != 2 {
+            return Err(start.error(format!(
+                "expected a pair of packets, found {}",
+                packets.len()
+            )));
+        }
+        Ok(packets)
+    };
+    parse_all(input, blocks(pair))
+}
+
+pub fn sum_of_ordered_pair_indices(pairs: &[Vec<Item>]) -> usize {
+    let right_order_indices = pairs.iter().enumerate().filter_map(|(index, pair)| {
+        let ordering = pair[0].partial_cmp(&pair[1]).unwrap();
+        debug!(pair = index + 1, ?ordering);
+        (ordering == Ordering::Less).then_some(index + 1)
+    });
+    right_order_indices.sum()
+}
+
+pub fn decoder_key(received_packets: impl IntoIterator<Item = Item>) -> usize {
+    let dividers = vec![
+        Item::List(vec![Item::List(vec![Item::Value(2)])]),
+        Item::List(vec![Item::List(vec![Item::Value(6)])]),
+    ];
+    let mut all_packets = dividers.clone();
+    all_packets.extend(received_packets);
+    all_packets.sort();
+
+    let divider_indices = dividers
+        .iter()
+        .map(|divider| {
+            all_packets
+                .iter()
+                .enumerate()
+                .find(|(_index, packet)| divider == *packet)
+                .unwrap()
+        })
+        .map(|(index, _packet)| index + 1);
+    divider_indices.product()
+}
+
+pub struct DistressSignal {
+    pairs: Vec<Vec<Item>>,
+}
+
+impl Solution for DistressSignal {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            pairs: parse_pairs(input)?,
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        Ok(sum_of_ordered_pair_indices(&self.pairs).into())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        let received_packets = self.pairs.iter().flatten().cloned();
+        Ok(decoder_key(received_packets).into())
+    }
+}
+
+/// Generates `size` pairs of packets; real inputs have 150.
+///
+/// Packets hold up to 20 values and lists, but nest up to `4 + size / 10` lists deep, and never
+/// more than half of [`MAX_DEPTH`], through chains of lists with a single element. The right
+/// packet is usually a small change to the left one somewhere deep inside, so comparisons have to
+/// descend far.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let max_depth = (4 + size / 10).min(MAX_DEPTH / 2);
+    let packet = |rng: &mut Rng| {
+        let mut budget = rng.range(1..=20) as usize;
+        Item::List(vec![random_item(rng, max_depth, &mut budget)])
+    };
+    let pairs: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let left = packet(rng);
+            let right = if rng.chance(0.2) {
+                packet(rng)
+            } else {
+                mutate(rng, &left)
+            };
+            format!("{left}\n{right}\n")
+        })
+        .collect();
+    pairs.join("\n")
+}
+
+/// A value or a list of at most `budget` values and lists, nested at most `depth` deep.
+fn random_item(rng: &mut Rng, depth: usize, budget: &mut usize) -> Item {
+    *budget = budget.saturating_sub(1);
+    let wrappers = if depth > 1 && rng.chance(0.1) {
+        rng.index(depth)
+    } else {
+        0
+    };
+    let depth = depth - wrappers;
+    let mut item = if depth == 0 || *budget == 0 || rng.chance(0.3) {
+        Item::Value(rng.range(0..=10) as u32)
+    } else {
+        let length = rng.range(0..=4);
+        Item::List(
+            (0..length)
+                .map(|_| random_item(rng, depth - 1, budget))
+                .collect(),
+        )
+    };
+    for _ in 0..wrappers {
+        item = Item::List(vec![item]);
+    }
+    item
+}
+
+/// Changes one value or list somewhere inside `item`.
+fn mutate(rng: &mut Rng, item: &Item) -> Item {
+    match item {
+        Item::List(items) if !items.is_empty() && rng.chance(0.8) => {
+            let mut items = items.clone();
+            let index = rng.index(items.len());
+            items[index] = mutate(rng, &items[index]);
+            Item::List(items)
+        }
+        Item::List(items) => {
+            let mut items = items.clone();
+            if items.is_empty() || rng.chance(0.5) {
+                items.push(Item::Value(rng.range(0..=10) as u32));
+            } else {
+                items.pop();
+            }
+            Item::List(items)
+        }
+        Item::Value(value) => Item::Value((value + 1 + rng.index(10) as u32) % 11),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::read_to_string;
+
+    use super::*;
+
+    #[test]
+    #[ignore = "requires puzzle input"]
+    fn parsing() {
+        let input = read_to_string("input").unwrap();
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            let item = line.parse::<Item>().unwrap();
+            assert_eq!(line, item.to_string());
+        }
+    }
+
+    #[test]
+    fn example_order() {
+        let input = read_to_string("example").unwrap();
+        let pairs = parse_pairs(&input).unwrap();
+        let indices_where = |ordering| {
+            let indices = pairs.iter().enumerate().filter_map(|(index, pair)| {
+                (pair[0].cmp(&pair[1]) == ordering).then_some(index + 1)
+            });
+            indices.collect::<Vec<_>>()
+        };
+        assert_eq!(indices_where(Ordering::Less), [1, 2, 4, 6]);
+        assert_eq!(indices_where(Ordering::Greater), [3, 5, 7, 8]);
+
+        let dividers = ["[[2]]", "[[6]]"].map(|divider| divider.parse::<Item>().unwrap());
+        let mut packets: Vec<Item> = pairs.into_iter().flatten().chain(dividers).collect();
+        packets.sort();
+        let sorted = read_to_string("output2").unwrap();
+        let packets: Vec<String> = packets.iter().map(Item::to_string).collect();
+        assert_eq!(packets, sorted.lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[ignore = "requires puzzle input"]
+    fn input() {
+        let input = read_to_string("input").unwrap();
+        let pairs: Vec<(&str, Vec<_>)> = input
+            .split("\n\n")
+            .map(|pair| {
+                (
+                    pair,
+                    pair.lines()
+                        .map(|line| line.parse::<Item>().unwrap())
+                        .collect(),
+                )
+            })
+            .collect();
+        let right_order = pairs.iter().map(|(original, pair)| {
+            let ordered = pair[0].partial_cmp(&pair[1]).unwrap() == Ordering::Less;
+            (original, ordered)
+        });
+        let output = read_to_string("output").unwrap();
+        let expected_output = output.lines().map(|line| line.parse::<bool>().unwrap());
+        right_order
+            .zip(expected_output)
+            .for_each(|((original, actual), expected)| assert_eq!(actual, expected, "{original}"));
+    }
+
+    #[test]
+    fn invalid_packets() {
+        let error = "[1,[2,x]]".parse::<Item>().unwrap_err();
+        assert_eq!(error.column, 7);
+
+        let error = "[1,2".parse::<Item>().unwrap_err();
+        assert_eq!(error.column, 5);
+
+        let error = "[1]]".parse::<Item>().unwrap_err();
+        assert_eq!(error.column, 4);
+
+        let error = parse_pairs("[1]\n[2]\n\n[3]\n[4;]\n").unwrap_err();
+        assert_eq!((error.line, error.column), (5, 3));
+
+        let error = parse_pairs("[1]\n[2]\n\n[3]\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+
+        let deep = "[".repeat(10 * MAX_DEPTH) + &"]".repeat(10 * MAX_DEPTH);
+        let error = deep.parse::<Item>().unwrap_err();
+        assert_eq!(error.column, MAX_DEPTH + 1);
+    }
+
+    #[test]
+    fn generated() {
+        let input = generate(&mut Rng::new(1), 100);
+        let pairs = parse_pairs(&input).unwrap();
+        assert_eq!(pairs.len(), 100);
+        let ordered = sum_of_ordered_pair_indices(&pairs);
+        assert!(0 < ordered && ordered < 100 * 101 / 2);
+        assert!(decoder_key(pairs.into_iter().flatten()) > 0);
+    }
+
+    #[test]
+    fn generated_deep() {
+        let input = generate(&mut Rng::new(1), 1500);
+        let pairs = parse_pairs(&input).unwrap();
+        assert_eq!(pairs.len(), 1500);
+        sum_of_ordered_pair_indices(&pairs);
+        decoder_key(pairs.into_iter().flatten());
+
+        let deepest = "[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH);
+        let packet = deepest.parse::<Item>().unwrap();
+        assert_eq!(packet.cmp(&packet.clone()), Ordering::Equal);
+    }
+}
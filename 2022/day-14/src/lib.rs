@@ -0,0 +1,386 @@
+use std::{
+    fmt::{self, Display, Write},
+    ops::RangeInclusive,
+};
+
+use aoc_common::{
+    lines, literal, parse_all, separated, unsigned, Answer, Cursor, Day, Grid, ParseError, Part,
+    Point, Recorder, Rng, Solution, Vector,
+};
+use eyre::Result;
+
+pub const DAY: Day =
+    Day::new::<RegolithReservoir>(14, "Regolith Reservoir", env!("CARGO_MANIFEST_DIR"))
+        .with_example(include_str!("../example"))
+        .with_generator(generate);
+
+/// How far down rocks may be.
+///
+/// Rocks can be anywhere to the sides, as [`paths_to_cave`] only keeps the columns sand can
+/// reach. The depth is the limit: part 2 piles up about `depth²` grains that each fall up to
+/// `depth` rows, so a much deeper cave would take minutes instead of milliseconds. Real inputs
+/// reach about 170 deep.
+pub const MAX_Y: i64 = 1_000;
+
+/// Parses a position like `498,4`.
+pub fn parse_position(cursor: &mut Cursor) -> Result<Point, ParseError> {
+    let start = *cursor;
+    let x: u32 = unsigned(cursor)?;
+    literal(",")(cursor)?;
+    let y: u32 = unsigned(cursor)?;
+    let position = Point::new(x.into(), y.into());
+    if position.y > MAX_Y {
+        return Err(start.error(format!(
+            "rocks have to be at most {MAX_Y} deep, found {x},{y}"
+        )));
+    }
+    Ok(position)
+}
+
+/// Parses a path like `498,4 -> 498,6 -> 496,6`, which only runs horizontally or vertically.
+pub fn parse_rock_path<'a>(cursor: &mut Cursor<'a>) -> Result<Vec<Point>, ParseError> {
+    let located_position = |cursor: &mut Cursor<'a>| Ok((*cursor, parse_position(cursor)?));
+    let positions = separated(located_position, " -> ")(cursor)?;
+    for pair in positions.windows(2) {
+        let [(_, from), (to_at, to)] = pair else {
+            unreachable!("windows of two")
+        };
+        if from.x != to.x && from.y != to.y {
+            return Err(to_at.error("rock paths have to run horizontally or vertically"));
+        }
+    }
+    Ok(positions
+        .into_iter()
+        .map(|(_, position)| position)
+        .collect())
+}
+
+pub fn parse_rock_paths(input: &str) -> Result<Vec<Vec<Point>>, ParseError> {
+    let paths = parse_all(input, lines(parse_rock_path))?;
+    if paths.is_empty() {
+        return Err(ParseError::end_of(input, "expected at least one rock path"));
+    }
+    Ok(paths)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_char(match self {
+            Tile::Air => '.',
+            Tile::Rock => '#',
+            Tile::Sand => 'o',
+        })
+    }
+}
+
+/// Builds a cave wide and deep enough for sand to pile up on the floor of part 2.
+///
+/// The cave ends two rows below the lowest rock, where part 2 puts the floor. Sand spreads at
+/// most one column per row, so it never gets further from the source's column than the floor is
+/// deep, and rocks beyond that are left out.
+pub fn paths_to_cave(paths: &[Vec<Point>]) -> Grid<Tile> {
+    let lowest_y = paths
+        .iter()
+        .flatten()
+        .map(|position| position.y)
+        .max()
+        .unwrap_or(0) as usize;
+    let height = lowest_y + 3;
+    let width = SAND_SOURCE.x as usize + height + 1;
+
+    let mut cave = Grid::new(width, height, Tile::Air);
+    let mut draw = |position: Point| {
+        if let Some(tile) = cave.get_mut(position) {
+            *tile = Tile::Rock;
+        }
+    };
+    for path in paths {
+        // a path of a single point is a rock of its own
+        draw(path[0]);
+        for pair in path.windows(2) {
+            let step = (pair[1] - pair[0]).signum();
+            let mut position = pair[0];
+            while position != pair[1] {
+                position += step;
+                draw(position);
+            }
+        }
+    }
+
+    cave
+}
+
+pub const SAND_SOURCE: Point = Point::new(500, 0);
+
+/// The row of the lowest rock, including those [`paths_to_cave`] left out to the sides.
+pub fn lowest_rock(cave: &Grid<Tile>) -> i64 {
+    cave.height() as i64 - 3
+}
+
+pub fn sand_rest_position(start: Point, cave: &Grid<Tile>, lowest_y: i64) -> Option<Point> {
+    let mut position = start;
+
+    'falling: while position.y < lowest_y {
+        for step in [Vector::new(0, 1), Vector::new(-1, 1), Vector::new(1, 1)] {
+            let next = position + step;
+            if cave.get(next).is_none_or(|tile| *tile == Tile::Air) {
+                position = next;
+                continue 'falling;
+            }
+        }
+        return Some(position);
+    }
+
+    None
+}
+
+/// The columns of the cave between `columns`, which is all a recorded frame needs to show.
+pub struct CaveView<'a> {
+    cave: &'a Grid<Tile>,
+    columns: RangeInclusive<i64>,
+}
+
+impl Display for CaveView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.cave.height() as i64 {
+            for x in self.columns.clone() {
+                match self.cave.get(Point::new(x, y)) {
+                    Some(tile) => tile.fmt(f)?,
+                    None => Tile::Air.fmt(f)?,
+                }
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+/// Drops sand until it falls into the abyss or blocks the source, recording the cave after
+/// every grain.
+pub fn part_1(mut cave: Grid<Tile>, recorder: &mut Recorder) -> Vec<Point> {
+    let rock_columns: Vec<i64> = cave
+        .iter()
+        .filter(|(_, tile)| **tile == Tile::Rock)
+        .map(|(point, _)| point.x)
+        .collect();
+    let left = rock_columns.iter().min().unwrap_or(&0) - 1;
+    let right = rock_columns.iter().max().unwrap_or(&0) + 1;
+    let columns = left..=right;
+
+    let mut rest_positions = Vec::new();
+    let lowest_y = lowest_rock(&cave);
+    while let Some(position) = sand_rest_position(SAND_SOURCE, &cave, lowest_y) {
+        rest_positions.push(position);
+        if position == SAND_SOURCE {
+            break;
+        }
+        cave[position] = Tile::Sand;
+        recorder.push(CaveView {
+            cave: &cave,
+            columns: columns.clone(),
+        });
+    }
+    recorder.finish(CaveView {
+        cave: &cave,
+        columns,
+    });
+
+    rest_positions
+}
+
+/// Drops sand on the floor until it blocks the source, recording the cave after every grain.
+pub fn part_2(mut cave: Grid<Tile>, recorder: &mut Recorder) -> Vec<Point> {
+    let mut rest_positions = Vec::new();
+    let floor_y = lowest_rock(&cave) + 2;
+    // sand piles up in a triangle at most as wide as twice its height
+    let columns = SAND_SOURCE.x - floor_y - 1..=SAND_SOURCE.x + floor_y + 1;
+
+    for x in 0..cave.width() as i64 {
+        cave[Point::new(x, floor_y)] = Tile::Rock;
+    }
+
+    loop {
+        let rest_position = sand_rest_position(SAND_SOURCE, &cave, floor_y);
+        match rest_position {
+            Some(position @ SAND_SOURCE) => {
+                rest_positions.push(position);
+                break;
+            }
+            Some(position) => {
+                rest_positions.push(position);
+                cave[position] = Tile::Sand;
+                recorder.push(CaveView {
+                    cave: &cave,
+                    columns: columns.clone(),
+                });
+            }
+            None => break,
+        }
+    }
+    recorder.finish(CaveView {
+        cave: &cave,
+        columns,
+    });
+
+    rest_positions
+}
+
+pub struct RegolithReservoir {
+    cave: Grid<Tile>,
+}
+
+impl Solution for RegolithReservoir {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let paths = parse_rock_paths(input)?;
+        Ok(Self {
+            cave: paths_to_cave(&paths),
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        self.visualize(Part::One, &mut Recorder::disabled())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        self.visualize(Part::Two, &mut Recorder::disabled())
+    }
+
+    fn visualize(&self, part: Part, recorder: &mut Recorder) -> Result<Answer> {
+        let rest_positions = match part {
+            Part::One => part_1(self.cave.clone(), recorder),
+            Part::Two => part_2(self.cave.clone(), recorder),
+        };
+        Ok(rest_positions.len().into())
+    }
+}
+
+/// Generates `size` rock paths; real inputs have about 150 and reach about 170 deep.
+///
+/// Paths reach `12 + size / 2` deep, at most [`MAX_Y`], so that the pile of part 2 stays within
+/// the columns of the cave. Every rock is at least 10 further down than it is away from the
+/// source's column, which keeps sand piling up in part 1 from reaching the source.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let depth = (12 + size as i64 / 2).min(MAX_Y);
+    let top = |x: i64| 10 + (x - SAND_SOURCE.x).abs();
+    (0..size.max(1))
+        .map(|_| {
+            let y = rng.range(10..=depth);
+            let mut position = Point::new(SAND_SOURCE.x + rng.range(10 - y..=y - 10), y);
+            let mut positions = vec![position];
+            for segment in 0..rng.range(1..=5) {
+                let length = rng.range(-10..=10);
+                if segment % 2 == 0 {
+                    let reach = position.y - 10;
+                    position.x =
+                        (position.x + length).clamp(SAND_SOURCE.x - reach, SAND_SOURCE.x + reach);
+                } else {
+                    position.y = (position.y + length).clamp(top(position.x), depth);
+                }
+                positions.push(position);
+            }
+            let positions: Vec<String> = positions
+                .iter()
+                .map(|position| format!("{},{}", position.x, position.y))
+                .collect();
+            positions.join(" -> ") + "\n"
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::read_to_string;
+
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = read_to_string("example").unwrap();
+        let paths = parse_rock_paths(&input).unwrap();
+        assert_eq!(
+            &paths,
+            &[
+                [Point::new(498, 4), Point::new(498, 6), Point::new(496, 6)].as_slice(),
+                &[
+                    Point::new(503, 4),
+                    Point::new(502, 4),
+                    Point::new(502, 9),
+                    Point::new(494, 9)
+                ]
+            ]
+        );
+        let cave = paths_to_cave(&paths);
+        let rest_positions = part_1(cave.clone(), &mut Recorder::disabled());
+        assert_eq!(
+            rest_positions[..6],
+            [
+                Point::new(500, 8),
+                Point::new(499, 8),
+                Point::new(501, 8),
+                Point::new(500, 7),
+                Point::new(498, 8),
+                Point::new(499, 7)
+            ]
+        );
+        assert_eq!(rest_positions.len(), 24);
+        assert_eq!(part_2(cave, &mut Recorder::disabled()).len(), 93);
+    }
+
+    #[test]
+    fn invalid_paths() {
+        let error = parse_rock_paths("498,4 -> 498,6\n503,4 -> 502\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 13));
+
+        let error = parse_rock_paths("498,4 -> 498,-6\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 14));
+
+        let error = parse_rock_paths("498,4 -> 498,6\n498,4 -> 500,7\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 10));
+        let error = parse_rock_paths("498,4 -> 500,6\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 10));
+
+        let error = parse_rock_paths("498,4 -> 0,4000000000\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 10));
+        let error = parse_rock_paths("498,4 -> 498,1001\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 10));
+        assert!(parse_rock_paths(&format!("0,{MAX_Y} -> 4000000000,{MAX_Y}\n")).is_ok());
+
+        // a single point is a valid path
+        let cave = paths_to_cave(&parse_rock_paths("500,5\n").unwrap());
+        assert_eq!(cave[Point::new(500, 5)], Tile::Rock);
+    }
+
+    #[test]
+    fn distant_rocks() {
+        // the far rock sets the floor, even though sand never gets close to it
+        let paths = parse_rock_paths("500,2\n5000,8 -> 5000,9\n").unwrap();
+        let cave = paths_to_cave(&paths);
+        assert_eq!(lowest_rock(&cave), 9);
+        assert_eq!(part_2(cave, &mut Recorder::disabled()).len(), 11 * 11 - 1);
+    }
+
+    #[test]
+    fn blocked_source() {
+        let cave = paths_to_cave(&parse_rock_paths("600,20 -> 400,20\n").unwrap());
+        assert_eq!(
+            part_1(cave.clone(), &mut Recorder::disabled()).len(),
+            20 * 20
+        );
+        assert_eq!(part_2(cave, &mut Recorder::disabled()).len(), 20 * 20);
+    }
+
+    #[test]
+    fn generated() {
+        let input = generate(&mut Rng::new(1), 50);
+        let cave = paths_to_cave(&parse_rock_paths(&input).unwrap());
+        let sand = part_1(cave.clone(), &mut Recorder::disabled()).len();
+        assert!(sand < part_2(cave, &mut Recorder::disabled()).len());
+    }
+}
@@ -0,0 +1,402 @@
+use std::collections::HashSet;
+
+use aoc_common::{
+    labelled, lines, parse_all, parse_parameter, signed, Answer, Cursor, Day, Direction, Parameter,
+    ParseError, Part, Point, Rng, Solution, Variant, Vector,
+};
+use eyre::{bail, OptionExt, Result};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use tracing::{debug, trace};
+
+pub const DAY: Day =
+    Day::new::<BeaconExclusionZone>(15, "Beacon Exclusion Zone", env!("CARGO_MANIFEST_DIR"))
+        .with_example(include_str!("../example"))
+        .with_parameters(&[
+            Parameter::new(
+                "row",
+                "2000000",
+                "Row in which part 1 counts the positions where no beacon can be",
+            )
+            .with_example("10"),
+            Parameter::new(
+                "max",
+                "4000000",
+                "Largest x and y coordinate of the distress signal in part 2",
+            )
+            .with_example("20"),
+        ])
+        .with_generator(generate);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Sensor {
+    pub position: Point,
+    pub beacon: Point,
+    pub clearing_distance: i64,
+}
+
+impl Sensor {
+    /// Parses a line like `Sensor at x=2, y=18: closest beacon is at x=-2, y=15`.
+    pub fn parse(cursor: &mut Cursor) -> Result<Self, ParseError> {
+        let x = labelled("Sensor at x=", signed)(cursor)?;
+        let y = labelled(", y=", signed)(cursor)?;
+        let beacon_x = labelled(": closest beacon is at x=", signed)(cursor)?;
+        let beacon_y = labelled(", y=", signed)(cursor)?;
+
+        let position = Point::new(x, y);
+        let beacon = Point::new(beacon_x, beacon_y);
+        let clearing_distance = position.manhattan(beacon);
+
+        Ok(Sensor {
+            position,
+            beacon,
+            clearing_distance,
+        })
+    }
+
+    pub fn in_range(&self, position: Point) -> bool {
+        self.position.manhattan(position) <= self.clearing_distance
+    }
+
+    pub fn range_on_row(&self, y: i64) -> i64 {
+        self.clearing_distance - (self.position.y - y).abs()
+    }
+}
+
+/// Returns 0 if no sensor reaches row `y`.
+pub fn blockers_in_row(sensors: &[Sensor], y: i64) -> usize {
+    let sensors: Vec<Sensor> = sensors
+        .iter()
+        .filter(|sensor| (sensor.position.y - y).abs() <= sensor.clearing_distance)
+        .inspect(|sensor| trace!(?sensor, "reaches row {y}"))
+        .cloned()
+        .collect();
+    let Some(min) = sensors
+        .iter()
+        .map(|sensor| sensor.position.x - sensor.clearing_distance)
+        .min()
+    else {
+        debug!(y, "no sensor reaches the row");
+        return 0;
+    };
+    let max = sensors
+        .iter()
+        .map(|sensor| sensor.position.x + sensor.clearing_distance)
+        .max()
+        .unwrap_or(min);
+    debug!(y, min, max, sensors = sensors.len(), "scanning row");
+
+    let count = (min..=max)
+        .filter(|x| {
+            sensors
+                .iter()
+                .any(|sensor| sensor.in_range(Point::new(*x, y)))
+        })
+        .count();
+
+    let beacons: HashSet<_> = sensors
+        .iter()
+        .filter_map(|sensor| (sensor.beacon.y == y).then_some(sensor.beacon.x))
+        .collect();
+
+    count - beacons.len()
+}
+
+// too slow for the full search area, kept as a benchmark variant
+pub fn find_distress_signal_brute_force(
+    sensors: &[Sensor],
+    min: Point,
+    max: Point,
+) -> Option<Point> {
+    (min.x..=max.x).into_par_iter().find_map_any(|x| {
+        (min.y..=max.y)
+            .into_par_iter()
+            .find_any(|y| {
+                !sensors
+                    .iter()
+                    .any(|sensor| sensor.in_range(Point::new(x, *y)))
+            })
+            .map(|y| Point::new(x, y))
+    })
+}
+
+pub enum Transition {
+    Enter,
+    Exit,
+}
+
+/// Sweeps every row from left to right over the ranges of the sensors on it.
+pub fn find_distress_signal_events(sensors: &[Sensor], min: Point, max: Point) -> Option<Point> {
+    for y in min.y..=max.y {
+        let mut events: Vec<_> = sensors
+            .iter()
+            .filter(|sensor| sensor.range_on_row(y) >= 0)
+            .flat_map(|sensor| {
+                let range = sensor.range_on_row(y);
+                [
+                    (sensor.position.x - range, Transition::Enter),
+                    // ranges are inclusive, so the row is free again right after the end
+                    (sensor.position.x + range + 1, Transition::Exit),
+                ]
+            })
+            .collect();
+        // entering first at the same x, so that adjacent ranges leave no gap
+        events.sort_by_key(|(x, transition)| (*x, matches!(transition, Transition::Exit)));
+
+        // the leftmost position that is not known to be covered yet
+        let mut free = min.x;
+        let mut covering = 0;
+        for (x, transition) in events {
+            if covering == 0 && x > free {
+                break;
+            }
+            match transition {
+                Transition::Enter => covering += 1,
+                Transition::Exit => {
+                    covering -= 1;
+                    if covering == 0 {
+                        free = free.max(x);
+                    }
+                }
+            }
+        }
+        if free <= max.x {
+            return Some(Point::new(free, y));
+        }
+    }
+
+    None
+}
+
+// wörks
+pub fn find_distress_signal_borders(sensors: &[Sensor], min: Point, max: Point) -> Option<Point> {
+    let mut positions = sensors
+        .iter()
+        .flat_map(|sensor| {
+            // walk each side of the diamond just outside the sensor's range
+            Direction::ALL.iter().flat_map(|direction| {
+                let corner = sensor.position + direction.vector() * (sensor.clearing_distance + 1);
+                let step = direction.turn_right().vector() - direction.vector();
+                (0..=sensor.clearing_distance).map(move |i| corner + step * i)
+            })
+        })
+        .filter(|position| {
+            (min.x..=max.x).contains(&position.x) && (min.y..=max.y).contains(&position.y)
+        });
+    positions.find(|position| !sensors.iter().any(|sensor2| sensor2.in_range(*position)))
+}
+
+pub fn tuning_frequency(position: Point) -> i64 {
+    position.x * 4_000_000 + position.y
+}
+
+pub struct BeaconExclusionZone {
+    sensors: Vec<Sensor>,
+    row: i64,
+    max: i64,
+}
+
+impl Solution for BeaconExclusionZone {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            sensors: parse_all(input, lines(Sensor::parse))?,
+            row: 2_000_000,
+            max: 4_000_000,
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        Ok(blockers_in_row(&self.sensors, self.row).into())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        self.distress_signal(find_distress_signal_borders)
+    }
+
+    fn variants(&self) -> &'static [Variant] {
+        &[
+            Variant {
+                name: "brute force",
+                part: Part::Two,
+            },
+            Variant {
+                name: "sweep events",
+                part: Part::Two,
+            },
+        ]
+    }
+
+    fn solve_variant(&self, name: &str) -> Result<Answer> {
+        match name {
+            "brute force" => self.distress_signal(find_distress_signal_brute_force),
+            "sweep events" => self.distress_signal(find_distress_signal_events),
+            _ => bail!("unknown variant '{name}'"),
+        }
+    }
+
+    fn set_parameter(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "row" => self.row = parse_parameter(name, value)?,
+            "max" => self.max = parse_parameter(name, value)?,
+            _ => bail!("unknown parameter '{name}'"),
+        }
+        Ok(())
+    }
+}
+
+impl BeaconExclusionZone {
+    fn distress_signal(
+        &self,
+        find: fn(&[Sensor], Point, Point) -> Option<Point>,
+    ) -> Result<Answer> {
+        let position = find(&self.sensors, Point::ORIGIN, Point::new(self.max, self.max))
+            .ok_or_eyre("no position for the distress signal found")?;
+        Ok(tuning_frequency(position).into())
+    }
+}
+
+/// Generates `size` sensors, at least four; real inputs have about 30.
+///
+/// Exactly one position of the search area of part 2 is out of range of every sensor.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    generate_sensors(rng, size, 4_000_000)
+        .0
+        .iter()
+        .map(|sensor| {
+            let Sensor {
+                position, beacon, ..
+            } = sensor;
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                position.x, position.y, beacon.x, beacon.y
+            )
+        })
+        .collect()
+}
+
+/// The sensors of [`generate`] for a search area from 0 to `area` and the position of the
+/// distress signal.
+///
+/// Four sensors diagonally around the signal cover all of the search area but the signal.
+/// The others are placed at random, with beacons closer to them than the signal.
+fn generate_sensors(rng: &mut Rng, size: usize, area: i64) -> (Vec<Sensor>, Point) {
+    let sensor = |position, beacon: Point| Sensor {
+        position,
+        beacon,
+        clearing_distance: position.manhattan(beacon),
+    };
+
+    let signal = Point::new(rng.range(1..=area - 1), rng.range(1..=area - 1));
+    let reach = [signal.x, signal.y, area - signal.x, area - signal.y]
+        .into_iter()
+        .max()
+        .expect("four distances");
+    let mut sensors: Vec<Sensor> = [(1, 1), (-1, 1), (1, -1), (-1, -1)]
+        .into_iter()
+        .map(|(x, y)| {
+            let position = signal + Vector::new(x, y) * reach;
+            sensor(position, signal + Vector::new(x, 0))
+        })
+        .collect();
+
+    while sensors.len() < size {
+        let position = Point::new(rng.range(0..=area), rng.range(0..=area));
+        let distance = position.manhattan(signal);
+        if distance < 2 {
+            continue;
+        }
+        let range = rng.range(distance / 2..=distance - 1);
+        let x = rng.range(-range..=range);
+        let y = (range - x.abs()) * rng.choose(&[-1, 1]);
+        sensors.push(sensor(position, position + Vector::new(x, y)));
+    }
+    rng.shuffle(&mut sensors);
+    (sensors, signal)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::read_to_string;
+
+    use aoc_common::check_property;
+
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = read_to_string("example").unwrap();
+        let sensors: Vec<Sensor> = input
+            .lines()
+            .map(|line| parse_all(line, Sensor::parse).unwrap())
+            .collect();
+        assert_eq!(blockers_in_row(&sensors, 10), 26);
+        assert_eq!(blockers_in_row(&sensors, -100), 0);
+        assert_eq!(blockers_in_row(&[], 10), 0);
+        let position = find_distress_signal_borders(&sensors, Point::ORIGIN, Point::new(20, 20));
+        assert_eq!(position, Some(Point::new(14, 11)));
+        assert_eq!(tuning_frequency(position.unwrap()), 56000011);
+    }
+
+    #[test]
+    fn invalid_sensors() {
+        let error = parse_all(
+            "Sensor at x=2, y=18: closest beacon at x=-2, y=15",
+            Sensor::parse,
+        )
+        .unwrap_err();
+        assert_eq!(error.column, 37);
+        assert_eq!(error.message, "expected 'is at x=', found 'at'");
+
+        let error = parse_all(
+            "Sensor at x=2, y=18: closest beacon is at x=-2, y=",
+            Sensor::parse,
+        )
+        .unwrap_err();
+        assert_eq!(error.column, 51);
+
+        let error = parse_all(
+            "Sensor at x=2, y=18: closest beacon is at x=-2, y=1.5",
+            Sensor::parse,
+        )
+        .unwrap_err();
+        assert_eq!(error.column, 52);
+    }
+
+    #[test]
+    fn generated() {
+        let mut rng = Rng::new(1);
+        let (sensors, signal) = generate_sensors(&mut rng, 10, 4_000_000);
+        let covered = |position| sensors.iter().any(|sensor| sensor.in_range(position));
+        assert!(!covered(signal));
+        for direction in Direction::ALL {
+            assert!(covered(signal + direction.vector()));
+        }
+        for _ in 0..1000 {
+            let position = Point::new(rng.range(0..=4_000_000), rng.range(0..=4_000_000));
+            assert!(position == signal || covered(position));
+        }
+
+        let input = generate(&mut Rng::new(1), 10);
+        let parsed = parse_all(&input, lines(Sensor::parse)).unwrap();
+        assert_eq!(parsed.len(), 10);
+    }
+
+    #[test]
+    fn strategies_agree() {
+        check_property(100, 40, |rng, size| {
+            let area = 2 + size as i64;
+            let (sensors, signal) = generate_sensors(rng, 4 + size / 4, area);
+            let max = Point::new(area, area);
+            for find in [
+                find_distress_signal_brute_force,
+                find_distress_signal_events,
+                find_distress_signal_borders,
+            ] {
+                assert_eq!(
+                    find(&sensors, Point::ORIGIN, max),
+                    Some(signal),
+                    "{sensors:?}"
+                );
+            }
+        });
+    }
+}
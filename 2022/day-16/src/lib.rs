@@ -0,0 +1,729 @@
+use std::{
+    borrow::BorrowMut,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{Display, Write},
+    sync::{Arc, Mutex},
+    vec,
+};
+
+use aoc_common::{
+    labelled, lines, literal, one_of, parse_all, parse_parameter, separated, take_while, unsigned,
+    Answer, Cursor, Day, Parameter, ParseError, Part, Rng, Solution, Variant,
+};
+use eyre::{bail, Result};
+use rayon::prelude::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use tracing::{debug, trace};
+
+pub const DAY: Day =
+    Day::new::<ProboscideaVolcanium>(16, "Proboscidea Volcanium", env!("CARGO_MANIFEST_DIR"))
+        .with_example(include_str!("../example"))
+        .with_parameters(&[
+            Parameter::new(
+                "part_1_minutes",
+                "30",
+                "Minutes until the volcano erupts in part 1",
+            ),
+            Parameter::new(
+                "part_2_minutes",
+                "26",
+                "Minutes left after teaching the elephant in part 2",
+            ),
+            Parameter::new(
+                "part_2_actors",
+                "2",
+                "Actors opening valves together in part 2",
+            ),
+        ])
+        .with_generator(generate);
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Position(pub [char; 2]);
+
+impl Position {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(name: &str) -> Self {
+        let mut chars = name.chars();
+        Self([chars.next().unwrap(), chars.next().unwrap()])
+    }
+
+    /// Parses a valve name like `AA`.
+    pub fn parse(cursor: &mut Cursor) -> Result<Self, ParseError> {
+        let start = *cursor;
+        let name = take_while(|next| next.is_alphanumeric())(cursor)?;
+        match name.chars().collect::<Vec<_>>()[..] {
+            [a, b] if a.is_ascii_uppercase() && b.is_ascii_uppercase() => Ok(Self([a, b])),
+            _ => Err(start.expected("a valve name like 'AA'")),
+        }
+    }
+}
+
+impl std::fmt::Debug for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}", self))
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_char(self.0[0])?;
+        f.write_char(self.0[1])
+    }
+}
+
+#[derive(Clone)]
+pub struct Node {
+    pub rate: u32,
+    pub exits: HashMap<Position, u32>,
+}
+
+impl std::fmt::Debug for Node {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, cost) in &self.exits {
+            f.write_fmt(format_args!("  {name:?}: {cost}\n"))?
+        }
+        Ok(())
+    }
+}
+
+pub struct Graph {
+    pub nodes: HashMap<Position, Node>,
+}
+
+impl std::fmt::Debug for Graph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, node) in &self.nodes {
+            f.write_fmt(format_args!("{name:?}: {node:?}\n"))?
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Step {
+    Move(Position, usize),
+    Open,
+}
+
+#[derive(Debug, Clone)]
+struct Actor {
+    position: Position,
+    time_remaining: u32,
+}
+
+#[derive(Debug, Clone)]
+struct RecursionState {
+    time_remaining: u32,
+    actors: Vec<Actor>,
+    closed_valves: HashSet<Position>,
+}
+
+impl Graph {
+    /// Parses lines like `Valve BB has flow rate=13; tunnels lead to valves CC, AA`.
+    pub fn parse<'a>(input: &'a str) -> Result<Self, ParseError> {
+        // valve names come with where they are, for errors found once all valves are known
+        let located_name = |cursor: &mut Cursor<'a>| Ok((*cursor, Position::parse(cursor)?));
+        let valve = |cursor: &mut Cursor<'a>| {
+            let name = labelled("Valve ", located_name)(cursor)?;
+            let rate: u32 = labelled(" has flow rate=", unsigned)(cursor)?;
+            literal("; ")(cursor)?;
+            one_of(&[
+                ("tunnels lead to valves ", ()),
+                ("tunnel leads to valve ", ()),
+            ])(cursor)?;
+            let exits = separated(located_name, ", ")(cursor)?;
+            Ok((name, rate, exits))
+        };
+
+        let mut nodes = HashMap::new();
+        let mut references = Vec::new();
+
+        for ((name_at, name), rate, exits) in parse_all(input, lines(valve))? {
+            references.extend(exits.iter().copied());
+            let exits = exits.into_iter().map(|(_, exit)| (exit, 1)).collect();
+            trace!(valve = %name, rate, exits = ?exits, "parsed valve");
+            if nodes.insert(name, Node { rate, exits }).is_some() {
+                return Err(name_at.error(format!("valve {name} is defined twice")));
+            }
+        }
+
+        if !nodes.contains_key(&Position::from_str("AA")) {
+            return Err(ParseError::end_of(input, "expected a valve named 'AA'"));
+        }
+        for (exit_at, exit) in references {
+            if !nodes.contains_key(&exit) {
+                return Err(exit_at.error(format!("tunnel leads to undefined valve {exit}")));
+            }
+        }
+
+        Ok(Graph { nodes })
+    }
+
+    /// Keeps only the valves worth opening and the start, connected by the length of the shortest
+    /// path between them. Valves that cannot be reached get no tunnel.
+    pub fn optimize(&self) -> Self {
+        let mut nodes = HashMap::new();
+        let relevant_nodes: HashMap<Position, Node> = self
+            .nodes
+            .iter()
+            .filter(|(name, node)| node.rate > 0 || name.0 == ['A', 'A'])
+            .map(|(name, node)| (*name, node.to_owned()))
+            .collect();
+
+        for (name, node) in relevant_nodes.iter() {
+            let mut queue = VecDeque::new();
+            queue.push_back(name);
+            let mut costs = HashMap::new();
+            costs.insert(name, 0);
+
+            while let Some(other_name) = queue.pop_front() {
+                let own_cost = costs[&other_name];
+                for exit in self.nodes[other_name].exits.iter() {
+                    let entry = costs.entry(exit.0).or_insert(u32::MAX);
+                    if *entry > own_cost + exit.1 {
+                        *entry = own_cost + exit.1;
+                        queue.push_back(exit.0);
+                    }
+                }
+            }
+
+            let exits = relevant_nodes
+                .iter()
+                .filter(|(name2, _node)| name != *name2)
+                .filter_map(|(name, _node)| Some((*name, *costs.get(name)?)))
+                .collect();
+
+            nodes.insert(
+                *name,
+                Node {
+                    rate: node.rate,
+                    exits,
+                },
+            );
+        }
+
+        Self { nodes }
+    }
+
+    #[cfg(test)]
+    fn pressure_released_on_route(&self, route: &[Step]) -> u32 {
+        let total_time = 30;
+        assert!(route.len() < total_time);
+        let mut position = Position::from_str("AA");
+        let mut opened = HashSet::new();
+        route
+            .iter()
+            .enumerate()
+            .map(|(index, step)| match step {
+                Step::Move(new_position, _) => {
+                    if !self.nodes[&position].exits.contains_key(new_position) {
+                        panic!("{new_position} not reachable from {position}. Route: {route:?}");
+                    }
+                    position = *new_position;
+                    0
+                }
+                Step::Open => {
+                    if !opened.insert(position) {
+                        panic!("{position} opened twice. Route: {route:?}");
+                    }
+                    let time_remaining = total_time - (1 + index);
+                    self.nodes[&position].rate * time_remaining as u32
+                }
+            })
+            .sum()
+    }
+
+    fn find_best_path_recurse(
+        &self,
+        position: Position,
+        time: u32,
+        score_so_far: u32,
+        best_score: Arc<Mutex<u32>>,
+        already_opened: &mut HashSet<Position>,
+    ) -> Option<(u32, Vec<Step>)> {
+        let remaining_valves = self
+            .nodes
+            .iter()
+            .filter(|(name, node)| node.rate > 0 && !already_opened.contains(name));
+        if time == 0 || remaining_valves.clone().next().is_none() {
+            return None;
+        }
+        let best_possible: u32 = remaining_valves
+            .clone()
+            .map(|(_name, node)| node.rate * (time - 1))
+            .sum();
+        {
+            if *best_score.lock().unwrap() > score_so_far + best_possible {
+                return None;
+            }
+        }
+        let open_step = (self.nodes[&position].rate > 0 && !already_opened.contains(&position))
+            .then(|| {
+                assert!(already_opened.insert(position));
+
+                let additional_release = (time - 1) * self.nodes[&position].rate;
+                let pressure = score_so_far + additional_release;
+                {
+                    let mut best_score = best_score.lock().unwrap();
+                    if *best_score < pressure {
+                        **best_score.borrow_mut() = pressure;
+                        debug!(pressure, "new best");
+                    }
+                }
+                let (pressure, steps) = self
+                    .find_best_path_recurse(
+                        position,
+                        time - 1,
+                        score_so_far + additional_release,
+                        best_score.clone(),
+                        already_opened,
+                    )
+                    .unwrap_or_default();
+                assert!(already_opened.remove(&position));
+                (pressure + additional_release, Step::Open, steps)
+            });
+
+        let move_steps = self.nodes[&position].exits.par_iter().filter_map(|exit| {
+            if time < *exit.1 {
+                return None;
+            }
+            let mut already_opened = already_opened.clone();
+            let (pressure, steps) = self
+                .find_best_path_recurse(
+                    *exit.0,
+                    time - exit.1,
+                    score_so_far,
+                    best_score.clone(),
+                    &mut already_opened,
+                )
+                .unwrap_or_default();
+            Some((pressure, Step::Move(*exit.0, 0), steps))
+        });
+
+        let (pressure, step, mut steps) = open_step
+            .into_par_iter()
+            .chain(move_steps)
+            .max_by_key(|(pressure, _step, _steps)| *pressure)?;
+
+        steps.insert(0, step);
+        Some((pressure, steps))
+    }
+
+    pub fn find_best_path(&self, position: Position, time: u32) -> Option<(u32, Vec<Step>)> {
+        let best_score = Arc::new(Mutex::new(0));
+        self.find_best_path_recurse(
+            position,
+            time,
+            0,
+            best_score,
+            &mut HashSet::from_iter(Some(position)),
+        )
+    }
+
+    fn find_best_path_2_recurse(
+        &self,
+        mut state: RecursionState,
+        score_so_far: u32,
+        best_score: Arc<Mutex<u32>>,
+    ) -> Option<(u32, Vec<Step>)> {
+        if state.time_remaining == 0 || state.closed_valves.is_empty() {
+            return None;
+        }
+        let best_possible_score: u32 = state
+            .closed_valves
+            .iter()
+            .map(|position| self.nodes[position].rate * (state.time_remaining - 1))
+            .sum();
+        {
+            if *best_score.lock().unwrap() > score_so_far + best_possible_score {
+                return None;
+            }
+        }
+        let (actor_index, time_advance) = state
+            .actors
+            .iter()
+            .map(|actor| actor.time_remaining)
+            .enumerate()
+            .min_by_key(|(_index, time_remaining)| *time_remaining)
+            .unwrap();
+        state
+            .actors
+            .iter_mut()
+            .for_each(|actor| actor.time_remaining -= time_advance);
+        let actor_position = state.actors[actor_index].position;
+        let exits = state
+            .closed_valves
+            .par_iter()
+            .filter(|position| **position != actor_position)
+            .filter_map(|position| {
+                Some((position, *self.nodes[&actor_position].exits.get(position)?))
+            });
+        state.time_remaining -= time_advance;
+
+        let steps = exits.filter_map(|(exit, cost)| {
+            if state.time_remaining < cost + 1 {
+                return None;
+            }
+            let mut new_state = state.clone();
+            new_state.closed_valves.remove(exit);
+            new_state.actors[actor_index].position = *exit;
+            new_state.actors[actor_index].time_remaining = cost + 1;
+
+            let additional_release = (state.time_remaining - (cost + 1)) * self.nodes[exit].rate;
+            let pressure = score_so_far + additional_release;
+            {
+                let mut best_score = best_score.lock().unwrap();
+                if *best_score < pressure {
+                    **best_score.borrow_mut() = pressure;
+                    debug!(pressure, "new best");
+                }
+            }
+
+            let (total_pressure, steps) = self
+                .find_best_path_2_recurse(
+                    new_state,
+                    score_so_far + additional_release,
+                    best_score.clone(),
+                )
+                .unwrap_or_default();
+            Some((
+                total_pressure + additional_release,
+                Step::Move(*exit, actor_index),
+                steps,
+            ))
+        });
+
+        let (pressure, step, mut steps) =
+            steps.max_by_key(|(pressure, _step, _steps)| *pressure)?;
+
+        steps.insert(0, step);
+        Some((pressure, steps))
+    }
+
+    pub fn find_best_path_2(
+        &self,
+        position: Position,
+        number_of_actors: usize,
+        time: u32,
+    ) -> Option<(u32, Vec<Step>)> {
+        let best_score = Arc::new(Mutex::new(0));
+        let state = RecursionState {
+            time_remaining: time,
+            actors: vec![
+                Actor {
+                    position,
+                    time_remaining: 0,
+                };
+                number_of_actors
+            ],
+            closed_valves: HashSet::from_iter(self.nodes.keys().cloned()),
+        };
+        self.find_best_path_2_recurse(state, 0, best_score)
+    }
+}
+
+pub struct ProboscideaVolcanium {
+    graph: Graph,
+    part_1_minutes: u32,
+    part_2_minutes: u32,
+    part_2_actors: usize,
+}
+
+impl Solution for ProboscideaVolcanium {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            graph: Graph::parse(input)?.optimize(),
+            part_1_minutes: 30,
+            part_2_minutes: 26,
+            part_2_actors: 2,
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        // no plan means there is no valve worth opening
+        let pressure = self
+            .graph
+            .find_best_path_2(Position::from_str("AA"), 1, self.part_1_minutes)
+            .map_or(0, |(pressure, _steps)| pressure);
+        Ok(pressure.into())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        let pressure = self
+            .graph
+            .find_best_path_2(
+                Position::from_str("AA"),
+                self.part_2_actors,
+                self.part_2_minutes,
+            )
+            .map_or(0, |(pressure, _steps)| pressure);
+        Ok(pressure.into())
+    }
+
+    fn variants(&self) -> &'static [Variant] {
+        &[Variant {
+            name: "single actor",
+            part: Part::One,
+        }]
+    }
+
+    fn solve_variant(&self, name: &str) -> Result<Answer> {
+        match name {
+            "single actor" => {
+                let pressure = self
+                    .graph
+                    .find_best_path(Position::from_str("AA"), self.part_1_minutes)
+                    .map_or(0, |(pressure, _steps)| pressure);
+                Ok(pressure.into())
+            }
+            _ => bail!("unknown variant '{name}'"),
+        }
+    }
+
+    fn set_parameter(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "part_1_minutes" => self.part_1_minutes = parse_parameter(name, value)?,
+            "part_2_minutes" => self.part_2_minutes = parse_parameter(name, value)?,
+            "part_2_actors" => {
+                self.part_2_actors = parse_parameter(name, value)?;
+                if self.part_2_actors == 0 {
+                    bail!("at least one actor has to open valves");
+                }
+            }
+            _ => bail!("unknown parameter '{name}'"),
+        }
+        Ok(())
+    }
+}
+
+/// Generates `size` valves, between 2 and 676; real inputs have about 60, 15 of which release
+/// pressure.
+///
+/// The tunnels form a random tree with a few shortcuts, so every valve can be reached from
+/// 'AA'. About a quarter of the valves release pressure, which is what the search branches on.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(2, 26 * 26);
+    let mut names: Vec<String> = ('A'..='Z')
+        .flat_map(|first| ('A'..='Z').map(move |second| format!("{first}{second}")))
+        .skip(1)
+        .collect();
+    rng.shuffle(&mut names);
+    names.insert(0, "AA".to_string());
+    names.truncate(size);
+
+    let mut tunnels: Vec<Vec<usize>> = vec![Vec::new(); size];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for valve in 1..size {
+        connect(valve, rng.index(valve));
+    }
+    for _ in 0..size / 4 {
+        connect(rng.index(size), rng.index(size));
+    }
+
+    let mut valves: Vec<String> = (0..size)
+        .map(|valve| {
+            let rate = if valve != 0 && rng.chance(0.25) {
+                rng.range(3..=25)
+            } else {
+                0
+            };
+            let exits: Vec<&str> = tunnels[valve]
+                .iter()
+                .map(|exit| names[*exit].as_str())
+                .collect();
+            let tunnels = if exits.len() == 1 {
+                "tunnel leads to valve"
+            } else {
+                "tunnels lead to valves"
+            };
+            format!(
+                "Valve {} has flow rate={rate}; {tunnels} {}\n",
+                names[valve],
+                exits.join(", ")
+            )
+        })
+        .collect();
+    rng.shuffle(&mut valves);
+    valves.concat()
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::read_to_string;
+
+    use aoc_common::check_property;
+
+    use super::*;
+
+    fn move_to(position: &str) -> Step {
+        Step::Move(Position::from_str(position), 0)
+    }
+
+    fn open() -> Step {
+        Step::Open
+    }
+
+    #[test]
+    fn example() {
+        let input = read_to_string("example").unwrap();
+        let graph = Graph::parse(&input).unwrap();
+        let total_pressure_released = graph.pressure_released_on_route(&[
+            move_to("DD"),
+            open(),
+            move_to("CC"),
+            move_to("BB"),
+            open(),
+            move_to("AA"),
+            move_to("II"),
+            move_to("JJ"),
+            open(),
+            move_to("II"),
+            move_to("AA"),
+            move_to("DD"),
+            move_to("EE"),
+            move_to("FF"),
+            move_to("GG"),
+            move_to("HH"),
+            open(),
+            move_to("GG"),
+            move_to("FF"),
+            move_to("EE"),
+            open(),
+            move_to("DD"),
+            move_to("CC"),
+            open(),
+        ]);
+        assert_eq!(total_pressure_released, 1651);
+        assert_eq!(
+            graph
+                .find_best_path(Position::from_str("AA"), 30)
+                .unwrap()
+                .0,
+            1651
+        );
+    }
+
+    #[test]
+    fn example_2() {
+        let input = read_to_string("example").unwrap();
+        let graph = Graph::parse(&input).unwrap();
+        let graph = graph.optimize();
+
+        let (pressure, _) = graph
+            .find_best_path_2(Position::from_str("AA"), 1, 30)
+            .unwrap();
+        assert_eq!(pressure, 1651);
+
+        let (pressure, _) = graph
+            .find_best_path_2(Position::from_str("AA"), 2, 26)
+            .unwrap();
+        assert_eq!(pressure, 1707);
+    }
+
+    #[test]
+    #[ignore = "requires puzzle input"]
+    fn input() {
+        let input = read_to_string("input").unwrap();
+        let graph = Graph::parse(&input).unwrap();
+        let graph = graph.optimize();
+
+        let (pressure, _) = graph
+            .find_best_path_2(Position::from_str("AA"), 1, 30)
+            .unwrap();
+        assert_eq!(pressure, 1617);
+
+        let (pressure, _) = graph
+            .find_best_path_2(Position::from_str("AA"), 2, 26)
+            .unwrap();
+        assert_eq!(pressure, 2828);
+    }
+
+    #[test]
+    fn invalid_graph() {
+        let error =
+            Graph::parse("Valve AA has flow rate=0; tunnels lead to valves BB, CC\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 50));
+
+        let error =
+            Graph::parse("Valve AA has flow rate=0; tunnel leads to valve A1\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 49));
+
+        let error =
+            Graph::parse("Valve AA has flow rate=x; tunnel leads to valve AA\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 24));
+    }
+
+    #[test]
+    fn unreachable_valves() {
+        let graph = Graph::parse(
+            "Valve AA has flow rate=0; tunnel leads to valve AA\n\
+             Valve BB has flow rate=1; tunnel leads to valve BB\n",
+        )
+        .unwrap()
+        .optimize();
+        assert_eq!(
+            graph.find_best_path_2(Position::from_str("AA"), 1, 30),
+            None
+        );
+
+        let graph = Graph::parse(
+            "Valve AA has flow rate=0; tunnels lead to valves CC, DD\n\
+             Valve BB has flow rate=1; tunnel leads to valve CC\n\
+             Valve CC has flow rate=2; tunnel leads to valve AA\n\
+             Valve DD has flow rate=0; tunnel leads to valve DD\n",
+        )
+        .unwrap()
+        .optimize();
+        let (pressure, _) = graph
+            .find_best_path_2(Position::from_str("AA"), 2, 30)
+            .unwrap();
+        assert_eq!(pressure, 2 * 28);
+    }
+
+    #[test]
+    fn no_flow_rates() {
+        let solution = ProboscideaVolcanium::parse(
+            "Valve AA has flow rate=0; tunnel leads to valve BB\n\
+             Valve BB has flow rate=0; tunnel leads to valve AA\n",
+        )
+        .unwrap();
+        assert_eq!(solution.part_1().unwrap(), Answer::from(0));
+        assert_eq!(solution.part_2().unwrap(), Answer::from(0));
+        assert_eq!(
+            solution.solve_variant("single actor").unwrap(),
+            Answer::from(0)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        let input = generate(&mut Rng::new(1), 12);
+        let graph = Graph::parse(&input).unwrap();
+        assert_eq!(graph.nodes.len(), 12);
+        let (pressure, _) = graph
+            .optimize()
+            .find_best_path_2(Position::from_str("AA"), 1, 30)
+            .unwrap();
+        assert!(pressure > 0);
+    }
+
+    #[test]
+    fn strategies_agree() {
+        check_property(30, 12, |rng, size| {
+            let input = generate(rng, size + 1);
+            let graph = Graph::parse(&input).unwrap().optimize();
+            let start = Position::from_str("AA");
+            let single = graph
+                .find_best_path(start, 20)
+                .map(|(pressure, _)| pressure);
+            let actors = graph
+                .find_best_path_2(start, 1, 20)
+                .map(|(pressure, _)| pressure);
+            assert_eq!(single, actors, "{input}");
+        });
+    }
+}
@@ -0,0 +1,284 @@
+use std::fmt::{self, Display, Write};
+
+use aoc_common::{
+    character, line, parse_all, parse_parameter, repeated, Answer, Day, Direction, Grid, Parameter,
+    ParseError, Part, Point, Recorder, Rng, Solution, Vector,
+};
+use eyre::{bail, Result};
+use itertools::Itertools;
+
+pub const DAY: Day =
+    Day::new::<PyroclasticFlow>(17, "Pyroclastic Flow", env!("CARGO_MANIFEST_DIR"))
+        .with_example(include_str!("../example"))
+        .with_parameters(&[
+            Parameter::new("width", "7", "Width of the chamber, at least 6"),
+            Parameter::new("rocks", "2022", "Rocks that fall in part 1"),
+        ])
+        .with_generator(generate);
+
+pub fn parse_jet(symbol: char) -> Option<Direction> {
+    match symbol {
+        '<' => Some(Direction::Left),
+        '>' => Some(Direction::Right),
+        _ => None,
+    }
+}
+
+pub fn parse_jets(input: &str) -> Result<Vec<Direction>, ParseError> {
+    parse_all(input, line(repeated(character("'<' or '>'", parse_jet))))
+}
+
+/// Rock shapes relative to their bottom left corner; y grows downwards, so they extend to
+/// negative y.
+pub struct Rock;
+
+impl Rock {
+    pub const DASH: &'static [Point] = &[
+        Point::new(0, 0),
+        Point::new(1, 0),
+        Point::new(2, 0),
+        Point::new(3, 0),
+    ];
+
+    pub const PLUS: &'static [Point] = &[
+        Point::new(1, 0),
+        Point::new(0, -1),
+        Point::new(1, -1),
+        Point::new(2, -1),
+        Point::new(1, -2),
+    ];
+
+    pub const L: &'static [Point] = &[
+        Point::new(0, 0),
+        Point::new(1, 0),
+        Point::new(2, 0),
+        Point::new(2, -1),
+        Point::new(2, -2),
+    ];
+
+    pub const BAR: &'static [Point] = &[
+        Point::new(0, 0),
+        Point::new(0, -1),
+        Point::new(0, -2),
+        Point::new(0, -3),
+    ];
+
+    pub const SQUARE: &'static [Point] = &[
+        Point::new(0, 0),
+        Point::new(0, -1),
+        Point::new(1, 0),
+        Point::new(1, -1),
+    ];
+}
+
+/// The row of `blocked` that stores `point`, as rows count up from the floor at y = 0.
+fn cell(point: Point) -> Point {
+    Point::new(point.x, -point.y)
+}
+
+/// How many rows at the top of the tower a recorded frame shows.
+const FRAME_ROWS: usize = 24;
+
+pub struct Map {
+    /// Settled rock by height above the floor, so row 0 is the floor itself.
+    blocked: Grid<bool>,
+    rock: Vec<Point>,
+}
+
+impl Map {
+    pub fn new(width: usize) -> Self {
+        Self {
+            blocked: Grid::new(width, 1, true),
+            rock: Vec::new(),
+        }
+    }
+
+    fn fits(&self, rock: &[Point]) -> bool {
+        rock.iter()
+            .all(|position| self.blocked.get(cell(*position)) == Some(&false))
+    }
+
+    /// Drops rocks and returns the height of the tower, recording every move of the rocks.
+    pub fn part_1<I>(&mut self, jets: I, rock_count: usize, recorder: &mut Recorder) -> i64
+    where
+        I: Iterator<Item = Direction> + Clone,
+    {
+        let mut jets = Itertools::intersperse(jets.cycle(), Direction::Down);
+        let mut rocks = [Rock::DASH, Rock::PLUS, Rock::L, Rock::BAR, Rock::SQUARE]
+            .into_iter()
+            .cycle();
+
+        let mut top = 0;
+        for _rock_index in 0..rock_count {
+            let jets = jets.by_ref();
+            while self.blocked.height() <= top as usize + 8 {
+                self.blocked.push_row(vec![false; self.blocked.width()]);
+            }
+            self.rock = rocks
+                .next()
+                .unwrap()
+                .iter()
+                .map(|position| *position + Vector::new(2, -(top + 4)))
+                .collect();
+            recorder.push(self.top_rows(FRAME_ROWS));
+            loop {
+                let direction = jets.next().unwrap();
+                let new_rock: Vec<Point> = self
+                    .rock
+                    .iter()
+                    .map(|position| *position + direction.vector())
+                    .collect();
+                if self.fits(&new_rock) {
+                    self.rock = new_rock;
+                } else if direction == Direction::Down {
+                    break;
+                }
+                recorder.push(self.top_rows(FRAME_ROWS));
+            }
+            top = top.max(self.rock.iter().map(|position| -position.y).max().unwrap());
+            for position in self.rock.drain(..) {
+                self.blocked[cell(position)] = true;
+            }
+        }
+        recorder.finish(self.top_rows(FRAME_ROWS));
+
+        top
+    }
+
+    /// Renders only the given number of rows below the highest rock.
+    pub fn top_rows(&self, rows: usize) -> TopRows<'_> {
+        TopRows {
+            map: self,
+            rows: Some(rows),
+        }
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        TopRows {
+            map: self,
+            rows: None,
+        }
+        .fmt(f)
+    }
+}
+
+/// The upper part of a [`Map`], see [`Map::top_rows`].
+pub struct TopRows<'a> {
+    map: &'a Map,
+    rows: Option<usize>,
+}
+
+impl Display for TopRows<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Map { blocked, rock } = self.map;
+        let top = blocked
+            .rows()
+            .rposition(|row| row.contains(&true))
+            .unwrap_or(0)
+            .max(
+                rock.iter()
+                    .map(|position| -position.y as usize)
+                    .max()
+                    .unwrap_or(0),
+            );
+
+        let rows = blocked.rows().enumerate().take(top + 1).rev();
+        for (y, row) in rows.take(self.rows.unwrap_or(usize::MAX)) {
+            f.write_char('|')?;
+            for (x, blocked) in row.iter().enumerate() {
+                let c = if *blocked {
+                    '#'
+                } else if rock.contains(&Point::new(x as i64, -(y as i64))) {
+                    '@'
+                } else {
+                    '.'
+                };
+                f.write_char(c)?;
+            }
+            f.write_str("|\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct PyroclasticFlow {
+    jets: Vec<Direction>,
+    width: usize,
+    rocks: usize,
+}
+
+impl Solution for PyroclasticFlow {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            jets: parse_jets(input)?,
+            width: 7,
+            rocks: 2022,
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer> {
+        self.visualize(Part::One, &mut Recorder::disabled())
+    }
+
+    fn part_2(&self) -> Result<Answer> {
+        bail!("part 2 is not solved yet")
+    }
+
+    fn visualize(&self, part: Part, recorder: &mut Recorder) -> Result<Answer> {
+        match part {
+            Part::One => {
+                let mut map = Map::new(self.width);
+                Ok(map
+                    .part_1(self.jets.iter().copied(), self.rocks, recorder)
+                    .into())
+            }
+            Part::Two => self.part_2(),
+        }
+    }
+
+    fn set_parameter(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "width" => {
+                self.width = parse_parameter(name, value)?;
+                if self.width < 6 {
+                    bail!("the chamber must be at least 6 units wide to fit every rock");
+                }
+            }
+            "rocks" => self.rocks = parse_parameter(name, value)?,
+            _ => bail!("unknown parameter '{name}'"),
+        }
+        Ok(())
+    }
+}
+
+/// Generates a jet pattern of `size` jets; real inputs have about 10000.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| rng.choose(&['<', '>']))
+        .chain(Some('\n'))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn invalid_jets() {
+        let error = parse_jets(">><<v>\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+
+        assert!(parse_jets("\n").is_err());
+    }
+
+    #[test]
+    fn generated() {
+        let jets = parse_jets(&generate(&mut Rng::new(1), 1000)).unwrap();
+        assert_eq!(jets.len(), 1000);
+        let height = Map::new(7).part_1(jets.into_iter(), 100, &mut Recorder::disabled());
+        assert!((100..=100 * 4).contains(&height));
+    }
+}
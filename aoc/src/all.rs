@@ -0,0 +1,253 @@
+use std::time::{Duration, Instant};
+
+use aoc_common::{Compact, Day, InputSource, KnownAnswers, Parameters, Part};
+use eyre::{bail, Result};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+use crate::panics::{catch, QuietPanics};
+
+/// One part solved by `aoc run --all`.
+struct PartRun {
+    year: u16,
+    day: &'static Day,
+    part: Part,
+    outcome: Outcome,
+    /// The recorded answer for the input, if there is one.
+    expected: Option<String>,
+    /// Reading and parsing the input plus solving the part.
+    time: Duration,
+}
+
+enum Outcome {
+    Answer(String),
+    /// The part returned an error, like parts that are not solved yet do.
+    Error(String),
+    /// The part panicked, or its answers file could not be read.
+    Failure(String),
+    /// The input could not be read, e.g. because puzzle inputs are not checked in.
+    Missing(String),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Status {
+    Correct,
+    Wrong,
+    Failed,
+    /// The part returned an error but has no known answer, so it was never solved.
+    Unsolved,
+    /// There is no known answer to compare with.
+    Unchecked,
+    Skipped,
+}
+
+impl PartRun {
+    fn status(&self) -> Status {
+        match (&self.outcome, &self.expected) {
+            (Outcome::Missing(_), _) => Status::Skipped,
+            (Outcome::Failure(_), _) | (Outcome::Error(_), Some(_)) => Status::Failed,
+            (Outcome::Error(_), None) => Status::Unsolved,
+            (Outcome::Answer(answer), Some(expected)) if answer == expected => Status::Correct,
+            (Outcome::Answer(_), Some(_)) => Status::Wrong,
+            (Outcome::Answer(_), None) => Status::Unchecked,
+        }
+    }
+
+    /// The answer column, with errors and multi-line answers shortened to one line.
+    fn summary(&self) -> String {
+        match &self.outcome {
+            Outcome::Answer(answer) if answer.contains('\n') => {
+                format!("({} lines)", answer.lines().count())
+            }
+            Outcome::Answer(answer) => answer.clone(),
+            Outcome::Error(error) | Outcome::Failure(error) => format!("error: {error}"),
+            Outcome::Missing(error) => error.clone(),
+        }
+    }
+}
+
+/// Solves every part of `days` at once and prints a table of the answers by day.
+///
+/// Every part is a separate job on rayon's global pool, so days that use rayon themselves
+/// share its threads with the other days instead of starting more. Fails if any part fails or
+/// its answer does not match the known one; parts that were never solved do not count.
+pub fn run(days: &[(u16, &'static Day)], source: &InputSource, parts: &[Part]) -> Result<()> {
+    let start = Instant::now();
+    let jobs: Vec<_> = days
+        .iter()
+        .flat_map(|&(year, day)| parts.iter().map(move |&part| (year, day, part)))
+        .collect();
+    // panics end up in the table, so keep the default hook from printing them in between
+    let quiet = QuietPanics::install();
+    let mut runs: Vec<PartRun> = jobs
+        .into_par_iter()
+        .map(|(year, day, part)| solve(year, day, part, source))
+        .collect();
+    drop(quiet);
+    let wall_time = start.elapsed();
+    runs.sort_by_key(|run| (run.year, run.day.day, run.part.number()));
+
+    println!(
+        "{:4} {:>3} {:>4} {:>9}  {:15}  answer",
+        "year", "day", "part", "time", "status"
+    );
+    for run in &runs {
+        let (status, mut answer) = (run.status(), run.summary());
+        let label = match status {
+            Status::Correct => "ok",
+            Status::Wrong => "WRONG",
+            Status::Failed => "FAILED",
+            Status::Unsolved => "unsolved",
+            Status::Unchecked => "no known answer",
+            Status::Skipped => "skipped",
+        };
+        if let (Status::Wrong, Some(expected)) = (status, &run.expected) {
+            answer = format!("{answer}, expected {expected}");
+        }
+        println!(
+            "{:4} {:>3} {:>4} {:>9}  {label:15}  {answer}",
+            run.year,
+            run.day.day,
+            run.part.number(),
+            Compact(run.time).to_string()
+        );
+    }
+
+    let count = |wanted: Status| runs.iter().filter(|run| run.status() == wanted).count();
+    let (wrong, failed) = (count(Status::Wrong), count(Status::Failed));
+    println!(
+        "\nran {} parts in {}: {} ok, {wrong} not matching, {failed} failed, {} unsolved, \
+         {} unchecked, {} skipped",
+        runs.len(),
+        Compact(wall_time),
+        count(Status::Correct),
+        count(Status::Unsolved),
+        count(Status::Unchecked),
+        count(Status::Skipped)
+    );
+    if wrong + failed > 0 {
+        bail!("not matching their known answers: {wrong}, failed: {failed}");
+    }
+    Ok(())
+}
+
+fn solve(year: u16, day: &'static Day, part: Part, source: &InputSource) -> PartRun {
+    let start = Instant::now();
+    let known = match KnownAnswers::load(day) {
+        Ok(known) => known,
+        // nothing about the day can be checked, which must not pass for success
+        Err(error) => {
+            return PartRun {
+                year,
+                day,
+                part,
+                outcome: Outcome::Failure(error.to_string()),
+                expected: None,
+                time: start.elapsed(),
+            }
+        }
+    };
+    let name = source.name(day);
+    let expected = name
+        .as_deref()
+        .and_then(|name| known.get(name, part).map(String::from));
+    let outcome = match source.read(day) {
+        Ok(input) => {
+            // a panicking day should not take the others down with it
+            let result = catch(|| {
+                day.parse_with(&input, name.as_deref(), &Parameters::default())?
+                    .solve(part)
+            });
+            match result {
+                Ok(Ok(answer)) => Outcome::Answer(answer.to_string()),
+                Ok(Err(error)) => Outcome::Error(error.to_string()),
+                Err(panic) => Outcome::Failure(panic),
+            }
+        }
+        Err(error) => Outcome::Missing(error.to_string()),
+    };
+    PartRun {
+        year,
+        day,
+        part,
+        outcome,
+        expected,
+        time: start.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        env::temp_dir,
+        fs::{create_dir_all, remove_dir_all, write},
+        process,
+    };
+
+    use day_2022_01::CalorieCounting;
+
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn statuses() {
+        let year = registry::find_year(2022).unwrap();
+        let day = year.find(10).unwrap();
+        let run = solve(2022, day, Part::Two, &InputSource::Example);
+        assert_eq!(run.status(), Status::Correct);
+        assert_eq!(run.summary(), "(6 lines)");
+
+        let day = year.find(17).unwrap();
+        let run = solve(2022, day, Part::Two, &InputSource::Example);
+        assert_eq!(run.status(), Status::Unsolved);
+        assert!(run.summary().starts_with("error: "));
+        let regressed = PartRun {
+            outcome: Outcome::Error("part 1 broke".to_string()),
+            expected: Some("3068".to_string()),
+            ..solve(2022, day, Part::One, &InputSource::Example)
+        };
+        assert_eq!(regressed.status(), Status::Failed);
+        let panicked = PartRun {
+            outcome: Outcome::Failure("panicked: oops".to_string()),
+            expected: None,
+            ..solve(2022, day, Part::Two, &InputSource::Example)
+        };
+        assert_eq!(panicked.status(), Status::Failed);
+        let unchecked = PartRun {
+            outcome: Outcome::Answer("1".to_string()),
+            expected: None,
+            ..solve(2022, day, Part::Two, &InputSource::Example)
+        };
+        assert_eq!(unchecked.status(), Status::Unchecked);
+
+        let missing = InputSource::Path("does-not-exist".into());
+        assert_eq!(
+            solve(2022, day, Part::One, &missing).status(),
+            Status::Skipped
+        );
+
+        let run = PartRun {
+            outcome: Outcome::Answer("1".to_string()),
+            expected: Some("2".to_string()),
+            ..run
+        };
+        assert_eq!(run.status(), Status::Wrong);
+    }
+
+    #[test]
+    fn invalid_answers() {
+        let directory = temp_dir().join(format!("aoc-answers-{}", process::id()));
+        create_dir_all(&directory).unwrap();
+        write(directory.join("answers"), "example: 24000\n").unwrap();
+        let directory = directory.to_string_lossy().into_owned().leak();
+        let day = Box::leak(Box::new(Day::new::<CalorieCounting>(1, "", directory)));
+
+        let run = solve(2022, day, Part::One, &InputSource::Example);
+        remove_dir_all(&*directory).unwrap();
+        assert_eq!(run.status(), Status::Failed);
+        assert!(
+            run.summary().starts_with("error: invalid answers"),
+            "{}",
+            run.summary()
+        );
+    }
+}
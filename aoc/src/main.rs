@@ -0,0 +1,579 @@
+use std::{
+    env,
+    fs::write,
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use aoc_common::{
+    bench, save_answers, save_recording, solve, submit, unverified_examples, verify, BenchHistory,
+    Client, Compact, Config, Day, InputSource, Parameters, Part, Recorder, Verdict, Year,
+};
+use clap::{Args, Parser, Subcommand};
+use eyre::{bail, eyre, Context, Result};
+use output::Format;
+use scaffold::Scaffold;
+use tracing_subscriber::EnvFilter;
+
+mod all;
+mod output;
+mod panics;
+mod registry;
+mod scaffold;
+mod tui;
+mod watch;
+
+#[derive(Parser)]
+#[command(about = "Runs the Advent of Code solutions")]
+struct Arguments {
+    /// Show diagnostics of the solvers, e.g. `debug`, `day_2022_13=trace` or
+    /// `day_2022_16=debug,aoc_common=info`
+    ///
+    /// Falls back to the environment variable `AOC_LOG`. Nothing is shown by default.
+    #[arg(long, global = true, value_name = "FILTER")]
+    log: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a day on its puzzle input
+    Run {
+        #[command(flatten)]
+        day: DayArguments,
+        /// Only solve the given part (1 or 2)
+        #[arg(long)]
+        part: Option<Part>,
+        #[command(flatten)]
+        input: InputArguments,
+        #[command(flatten)]
+        parameters: ParameterArguments,
+        /// Record the answers as accepted for this input
+        #[arg(long)]
+        save: bool,
+        /// Solve every registered day at once, or every day of `--year`, and check the answers
+        #[arg(long, conflicts_with_all = ["number", "day", "input", "params", "params_file", "save", "format"])]
+        all: bool,
+        /// How to print the answers
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Re-solve all recorded answers and report the ones that changed
+    Verify {
+        /// Only verify these days
+        days: Vec<u8>,
+        /// Only verify the days of this year, the latest one if days are given
+        #[arg(long)]
+        year: Option<u16>,
+    },
+    /// Time parsing and each part of a day and compare with the previous run
+    Bench {
+        #[command(flatten)]
+        day: DayArguments,
+        /// Only time the given part (1 or 2)
+        #[arg(long)]
+        part: Option<Part>,
+        #[command(flatten)]
+        input: InputArguments,
+        #[command(flatten)]
+        parameters: ParameterArguments,
+        /// How often every step is repeated
+        #[arg(long, default_value_t = 10)]
+        iterations: usize,
+        /// Also time the alternative algorithms of the day
+        #[arg(long)]
+        variants: bool,
+    },
+    /// Record the simulation of a part as an animated GIF or an asciinema cast
+    Record {
+        #[command(flatten)]
+        day: DayArguments,
+        /// The part to record (1 or 2)
+        #[arg(long, default_value = "1")]
+        part: Part,
+        #[command(flatten)]
+        input: InputArguments,
+        #[command(flatten)]
+        parameters: ParameterArguments,
+        /// Where to write the recording, ending in `.gif` or `.cast`
+        #[arg(long, value_name = "PATH")]
+        output: PathBuf,
+        /// How long every frame is shown, in milliseconds
+        #[arg(long, default_value_t = 50)]
+        delay: u64,
+        /// Stop recording after this many frames, the simulation still runs to the end
+        #[arg(long, default_value_t = 2000)]
+        max_frames: usize,
+    },
+    /// Write a synthetic puzzle input for stress testing, e.g. 100 times the real size
+    Generate {
+        #[command(flatten)]
+        day: DayArguments,
+        /// Roughly how many elements the input has; each day documents what it counts
+        #[arg(long)]
+        size: usize,
+        /// The same seed gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Write the input to this file instead of stdout
+        #[arg(long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+    /// Solve a part on the day's input and submit the answer
+    Submit {
+        /// The day of the puzzle
+        day: u8,
+        /// The part to submit (1 or 2)
+        part: Part,
+        /// The year of the puzzle, by default the latest one with registered days
+        #[arg(long)]
+        year: Option<u16>,
+    },
+    /// Download the puzzle inputs of days that do not have one yet
+    Download {
+        /// Only download these days
+        days: Vec<u8>,
+        /// Only download the days of this year, the latest one if days are given
+        #[arg(long)]
+        year: Option<u16>,
+    },
+    /// Create the crate for a new day and register it with the runner
+    New {
+        year: u16,
+        day: u8,
+        /// The puzzle's title, which also names the solution type
+        #[arg(long)]
+        title: Option<String>,
+    },
+    /// List all registered days by year
+    List,
+    /// List the puzzle constants of a day that `--param` can change
+    Parameters {
+        #[command(flatten)]
+        day: DayArguments,
+    },
+    /// Rebuild and re-run a day whenever its sources, input or examples change
+    Watch {
+        #[command(flatten)]
+        day: DayArguments,
+        #[command(flatten)]
+        input: InputArguments,
+    },
+    /// Browse the days in a terminal dashboard, run them and look at their output
+    Tui {
+        /// Show the days of this year instead of the latest one
+        #[arg(long)]
+        year: Option<u16>,
+    },
+}
+
+#[derive(Args)]
+struct DayArguments {
+    /// The day of the puzzle
+    #[arg(value_name = "DAY")]
+    number: Option<u8>,
+    /// The day of the puzzle, instead of giving it as DAY
+    #[arg(long, conflicts_with = "number")]
+    day: Option<u8>,
+    /// The year of the puzzle, by default the latest one with registered days
+    #[arg(long)]
+    year: Option<u16>,
+}
+
+impl DayArguments {
+    /// Looks up the day, together with its year.
+    fn find(self) -> Result<(u16, &'static Day)> {
+        let day = self
+            .number
+            .or(self.day)
+            .ok_or_else(|| eyre!("no day given, e.g. `5` or `--day 5`"))?;
+        find_day(find_year(self.year)?, day)
+    }
+}
+
+#[derive(Args)]
+struct InputArguments {
+    /// Read the puzzle input from this file instead of the day's `input`, `-` for stdin
+    #[arg(long, value_name = "PATH", conflicts_with = "example")]
+    input: Option<InputSource>,
+    /// Use the example from the puzzle description as input
+    #[arg(long)]
+    example: bool,
+}
+
+#[derive(Args)]
+struct ParameterArguments {
+    /// Change a puzzle constant to solve a made-up variant, e.g. `--param row=10`
+    #[arg(long = "param", value_name = "NAME=VALUE")]
+    params: Vec<String>,
+    /// Read puzzle constants from a file of `name = value` lines, before any `--param`
+    #[arg(long, value_name = "PATH")]
+    params_file: Option<PathBuf>,
+}
+
+impl ParameterArguments {
+    fn parameters(self) -> Result<Parameters> {
+        let mut parameters = match self.params_file {
+            Some(path) => Parameters::load(&path)?,
+            None => Parameters::default(),
+        };
+        for assignment in &self.params {
+            parameters.assign(assignment)?;
+        }
+        Ok(parameters)
+    }
+}
+
+impl InputArguments {
+    fn source(self) -> InputSource {
+        match (self.input, self.example) {
+            (Some(source), _) => source,
+            (None, true) => InputSource::Example,
+            (None, false) => InputSource::Default,
+        }
+    }
+}
+
+/// The given year, or the latest one.
+fn find_year(year: Option<u16>) -> Result<&'static Year> {
+    match year {
+        Some(year) => {
+            registry::find_year(year).ok_or_else(|| eyre!("no days registered for {year}"))
+        }
+        None => Ok(registry::latest()),
+    }
+}
+
+fn find_day(year: &'static Year, day: u8) -> Result<(u16, &'static Day)> {
+    let registered = year
+        .find(day)
+        .ok_or_else(|| eyre!("no solution registered for day {day} of {}", year.year))?;
+    Ok((year.year, registered))
+}
+
+/// Downloads a missing default input, as long as a session token is configured.
+fn ensure_input(year: u16, day: &Day, source: &InputSource) -> Result<()> {
+    if *source != InputSource::Default || day.input_path().is_file() {
+        return Ok(());
+    }
+    let config = Config::load()?;
+    if config.session.is_none() {
+        return Ok(());
+    }
+    eprintln!("downloading the input of day {} of {year}", day.day);
+    Client::new(&config)?.fetch_input(year, day).map(|_| ())
+}
+
+/// The given days of a year; without days, all days of the year or of every year.
+fn find_days(year: Option<u16>, days: Vec<u8>) -> Result<Vec<(u16, &'static Day)>> {
+    if days.is_empty() && year.is_none() {
+        return Ok(registry::all_days().collect());
+    }
+    let year = find_year(year)?;
+    if days.is_empty() {
+        return Ok(year.days.iter().map(|day| (year.year, day)).collect());
+    }
+    days.into_iter().map(|day| find_day(year, day)).collect()
+}
+
+/// Where the sources of the runner and the days are, for commands that edit or build them.
+fn workspace_root() -> Result<&'static Path> {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .ok_or_else(|| eyre!("the runner is not part of a workspace"))
+}
+
+fn parts(part: Option<Part>) -> Vec<Part> {
+    match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    }
+}
+
+/// Sends tracing events matching `filter` to stderr; without a filter they are discarded.
+fn init_tracing(filter: Option<String>) -> Result<()> {
+    let Some(filter) = filter.or_else(|| env::var("AOC_LOG").ok()) else {
+        return Ok(());
+    };
+    let filter =
+        EnvFilter::try_new(&filter).wrap_err_with(|| format!("invalid log filter '{filter}'"))?;
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr)
+        .without_time()
+        .init();
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let arguments = Arguments::parse();
+    init_tracing(arguments.log)?;
+
+    match arguments.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            parameters,
+            save,
+            format,
+            all,
+        } => {
+            if all {
+                return all::run(
+                    &find_days(day.year, Vec::new())?,
+                    &input.source(),
+                    &parts(part),
+                );
+            }
+            let (year, day) = day.find()?;
+            let source = input.source();
+            let name = source.name(day);
+            let parameters = parameters.parameters()?;
+            if save && name.is_none() {
+                bail!("answers can only be saved for inputs in {}", day.directory);
+            }
+            if save && !parameters.is_empty() {
+                bail!("answers can only be saved with the puzzle's parameters");
+            }
+            ensure_input(year, day, &source)?;
+
+            let solved = solve(day, &source, &parts(part), &parameters, |solved| {
+                format.print(year, solved)
+            })?;
+            match name {
+                Some(name) if save => save_answers(day, &name, &solved),
+                _ => Ok(()),
+            }
+        }
+        Command::Bench {
+            day,
+            part,
+            input,
+            parameters,
+            iterations,
+            variants,
+        } => {
+            let (year, day) = day.find()?;
+            let source = input.source();
+            let parameters = parameters.parameters()?;
+            ensure_input(year, day, &source)?;
+            let measurements = bench(
+                day,
+                &source,
+                &parts(part),
+                &parameters,
+                iterations,
+                variants,
+            )?;
+            // timings of made-up variants are not comparable with the puzzle's
+            let name = source.name(day).filter(|_| parameters.is_empty());
+            let history = BenchHistory::load(day)?;
+
+            println!(
+                "{:24} {:>9} {:>9} {:>9}  previous",
+                "", "median", "min", "max"
+            );
+            for measurement in &measurements {
+                let timing = &measurement.timing;
+                let previous = name
+                    .as_deref()
+                    .and_then(|name| history.previous(name, &measurement.label))
+                    .map_or("-".to_string(), |entry| {
+                        let change = timing.median().as_secs_f64() / entry.median.as_secs_f64();
+                        format!(
+                            "{} ({:+.1}%)",
+                            Compact(entry.median),
+                            (change - 1.0) * 100.0
+                        )
+                    });
+                println!(
+                    "{:24} {:>9} {:>9} {:>9}  {previous}",
+                    measurement.label,
+                    Compact(timing.median()),
+                    Compact(timing.min()),
+                    Compact(timing.max())
+                );
+            }
+
+            match name {
+                Some(name) => BenchHistory::append(day, &name, &measurements),
+                None => {
+                    eprintln!(
+                        "note: results are only kept for inputs in {} with the puzzle's parameters",
+                        day.directory
+                    );
+                    Ok(())
+                }
+            }
+        }
+        Command::Verify { days, year } => {
+            let mut regressions = 0;
+            for (year, day) in find_days(year, days)? {
+                for verification in verify(day)? {
+                    let label = format!(
+                        "{year} day {:2} {} part {}",
+                        day.day,
+                        verification.input,
+                        verification.part.number()
+                    );
+                    match &verification.verdict {
+                        Verdict::Correct => println!("{label}: ok"),
+                        Verdict::Wrong { expected, actual } => {
+                            println!("{label}: WRONG\n  expected: {expected}\n  actual:   {actual}")
+                        }
+                        Verdict::Failed(error) => println!("{label}: FAILED: {error}"),
+                        Verdict::Skipped(reason) => println!("{label}: skipped, {reason}"),
+                    }
+                    regressions += usize::from(verification.is_regression());
+                }
+                for example in unverified_examples(day)? {
+                    println!("{year} day {:2} {example}: no known answers", day.day);
+                }
+            }
+
+            if regressions > 0 {
+                bail!("{regressions} known answers no longer match");
+            }
+            Ok(())
+        }
+        Command::Record {
+            day,
+            part,
+            input,
+            parameters,
+            output,
+            delay,
+            max_frames,
+        } => {
+            let (year, day) = day.find()?;
+            let source = input.source();
+            ensure_input(year, day, &source)?;
+            let solution = day.parse_with(
+                &source.read(day)?,
+                source.name(day).as_deref(),
+                &parameters.parameters()?,
+            )?;
+
+            let mut recorder = Recorder::new(Duration::from_millis(delay), max_frames);
+            let answer = solution.visualize(part, &mut recorder)?;
+            println!("{part}: {answer}");
+            let frames = recorder.into_frames();
+            save_recording(&frames, &output, &format!("Day {}: {}", day.day, day.title))?;
+            println!("recorded {} frames to {}", frames.len(), output.display());
+            Ok(())
+        }
+        Command::Generate {
+            day,
+            size,
+            seed,
+            output,
+        } => {
+            let (_, day) = day.find()?;
+            let input = day.generate(seed, size)?;
+            match output {
+                Some(path) => write(&path, input)
+                    .wrap_err_with(|| format!("failed to write {}", path.display())),
+                None => {
+                    print!("{input}");
+                    Ok(())
+                }
+            }
+        }
+        Command::Submit { day, part, year } => {
+            let (year, day) = find_day(find_year(year)?, day)?;
+            ensure_input(year, day, &InputSource::Default)?;
+            let client = Client::new(&Config::load()?)?;
+            let attempt = submit(&client, year, day, part)?;
+            println!("{part}: {} is {}", attempt.answer, attempt.outcome);
+            if attempt.retry_after > attempt.timestamp {
+                println!(
+                    "the next answer can be submitted in {}s",
+                    attempt.retry_after - attempt.timestamp
+                );
+            }
+            Ok(())
+        }
+        Command::Download { days, year } => {
+            let days = find_days(year, days)?;
+            let client = Client::new(&Config::load()?)?;
+            for (year, day) in days {
+                let cached = day.input_path().is_file();
+                client.fetch_input(year, day)?;
+                let state = if cached {
+                    "already there"
+                } else {
+                    "downloaded"
+                };
+                println!("{year} day {:2}: {state}", day.day);
+            }
+            Ok(())
+        }
+        Command::New { year, day, title } => {
+            let scaffold = Scaffold::new(year, day, title)?;
+            scaffold.create(workspace_root()?)?;
+            println!(
+                "created {}, paste the example into its `example` file",
+                scaffold.directory().display()
+            );
+            Ok(())
+        }
+        Command::List => {
+            for year in registry::YEARS {
+                println!("{}", year.year);
+                for day in year.days {
+                    println!("  {:2}: {}", day.day, day.title);
+                }
+            }
+            Ok(())
+        }
+        Command::Parameters { day } => {
+            let (_, day) = day.find()?;
+            if day.parameters.is_empty() {
+                println!("day {} has no parameters", day.day);
+            }
+            for parameter in day.parameters {
+                let example = parameter
+                    .example
+                    .map_or(String::new(), |example| format!(", example {example}"));
+                println!(
+                    "{} = {}{example}\n  {}",
+                    parameter.name, parameter.default, parameter.description
+                );
+            }
+            Ok(())
+        }
+        Command::Watch { day, input } => {
+            let (year, day) = day.find()?;
+            watch::watch(workspace_root()?, year, day, &input.source())
+        }
+        Command::Tui { year } => tui::run(find_year(year)?),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn arguments() {
+        Arguments::command().debug_assert();
+
+        let arguments = Arguments::try_parse_from(["aoc", "run", "--year", "2022", "--day", "5"]);
+        let Ok(Arguments {
+            command: Command::Run { day, .. },
+            ..
+        }) = arguments
+        else {
+            panic!("expected a run command");
+        };
+        assert_eq!(day.find().unwrap().1.day, 5);
+        assert!(Arguments::try_parse_from(["aoc", "run", "5", "--day", "6"]).is_err());
+        assert!(Arguments::try_parse_from(["aoc", "run", "5", "--all"]).is_err());
+        assert!(Arguments::try_parse_from(["aoc", "run", "--all", "--example"]).is_ok());
+    }
+}
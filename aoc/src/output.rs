@@ -0,0 +1,98 @@
+use aoc_common::{print_answer, Solved};
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// How `aoc run` writes answers to stdout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// `Part 1: 24000`
+    #[default]
+    Text,
+    /// One JSON object per line and part, for scripts and dashboards
+    Json,
+}
+
+/// A solved part as emitted by `--format json`.
+#[derive(Serialize)]
+struct JsonAnswer<'a> {
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: String,
+    /// `null` for inputs outside the day's directory, e.g. from stdin.
+    input: Option<&'a str>,
+    input_sha256: &'a str,
+    parse_ns: u64,
+    solve_ns: u64,
+    /// Only with the `count-allocations` feature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allocations: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allocated_bytes: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    peak_heap_bytes: Option<usize>,
+}
+
+impl Format {
+    pub fn print(self, year: u16, solved: &Solved) {
+        match self {
+            Format::Text => print_answer(solved),
+            Format::Json => println!("{}", to_json(year, solved)),
+        }
+    }
+}
+
+fn to_json(year: u16, solved: &Solved) -> String {
+    let answer = JsonAnswer {
+        year,
+        day: solved.day,
+        part: solved.part.number(),
+        answer: solved.answer.to_string(),
+        input: solved.input.as_deref(),
+        input_sha256: &solved.input_hash,
+        parse_ns: solved.parse_time.as_nanos().try_into().unwrap_or(u64::MAX),
+        solve_ns: solved.solve_time.as_nanos().try_into().unwrap_or(u64::MAX),
+        allocations: solved.allocations.map(|allocations| allocations.count),
+        allocated_bytes: solved.allocations.map(|allocations| allocations.bytes),
+        peak_heap_bytes: solved.allocations.map(|allocations| allocations.peak),
+    };
+    serde_json::to_string(&answer).expect("answers serialize to JSON")
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use aoc_common::{Allocations, Answer, Part};
+
+    use super::*;
+
+    #[test]
+    fn json() {
+        let solved = Solved {
+            day: 10,
+            part: Part::Two,
+            answer: Answer::from(vec!["##..".to_string(), "#..#".to_string()]),
+            input: Some("example".to_string()),
+            input_hash: "ab12".to_string(),
+            parse_time: Duration::from_micros(3),
+            solve_time: Duration::from_millis(2),
+            allocations: None,
+        };
+        assert_eq!(
+            to_json(2022, &solved),
+            r###"{"year":2022,"day":10,"part":2,"answer":"##..\n#..#","input":"example","input_sha256":"ab12","parse_ns":3000,"solve_ns":2000000}"###
+        );
+
+        let solved = Solved {
+            allocations: Some(Allocations {
+                count: 4,
+                bytes: 120,
+                peak: 80,
+            }),
+            ..solved
+        };
+        assert!(to_json(2022, &solved)
+            .ends_with(r#""allocations":4,"allocated_bytes":120,"peak_heap_bytes":80}"#));
+    }
+}
@@ -0,0 +1,156 @@
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    panic::{catch_unwind, set_hook, take_hook, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        OnceLock,
+    },
+};
+
+thread_local! {
+    /// Where the last panic on this thread happened, until [`catch`] takes it.
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+    /// How many [`QuietPanics`] this thread holds.
+    static GUARDS: Cell<usize> = const { Cell::new(0) };
+    /// How many calls of [`catch`] this thread is in.
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+}
+
+/// How many [`QuietPanics`] are alive on any thread.
+static QUIET: AtomicUsize = AtomicUsize::new(0);
+
+/// Installs the hook that records where panics happen, once for the whole process.
+///
+/// The hook stays for good, so that guards on different threads cannot put back each other's
+/// hooks. Panics it keeps quiet are not passed on to the hook it replaced.
+fn install_hook() {
+    static HOOK: OnceLock<()> = OnceLock::new();
+    HOOK.get_or_init(|| {
+        let previous = take_hook();
+        set_hook(Box::new(move |info| {
+            if let Some(location) = info.location() {
+                LOCATION.set(Some(location.to_string()));
+            }
+            let caught = CATCHING.get() > 0;
+            let quiet = QUIET.load(Ordering::SeqCst) > 0 && GUARDS.get() == 0;
+            if !caught && !quiet {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Keeps panics on other threads from being printed until it is dropped.
+///
+/// Panics on the thread that holds it still go to the previous hook, so a crash of the
+/// program itself is reported as before.
+pub struct QuietPanics(());
+
+impl QuietPanics {
+    pub fn install() -> Self {
+        install_hook();
+        GUARDS.set(GUARDS.get() + 1);
+        QUIET.fetch_add(1, Ordering::SeqCst);
+        Self(())
+    }
+}
+
+impl Drop for QuietPanics {
+    fn drop(&mut self) {
+        QUIET.fetch_sub(1, Ordering::SeqCst);
+        GUARDS.set(GUARDS.get() - 1);
+    }
+}
+
+/// Runs `f`, turning a panic into a message like `panicked at src/lib.rs:3:5: oops`.
+///
+/// The location is only known for panics on this thread. A panic in a nested rayon task is
+/// caught on another thread than the one it happened on, so it is reported without one.
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    install_hook();
+    LOCATION.take();
+    CATCHING.set(CATCHING.get() + 1);
+    let result = catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(CATCHING.get() - 1);
+    let location = LOCATION.take();
+    result.map_err(|panic| {
+        let message = panic_message(&*panic);
+        match location {
+            Some(location) => format!("panicked at {location}: {message}"),
+            None => format!("panicked: {message}"),
+        }
+    })
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown cause"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+    use super::*;
+
+    #[test]
+    fn locations() {
+        let error = catch(|| panic!("oops")).unwrap_err();
+        let line = line!() - 1;
+        assert!(
+            error.starts_with(&format!("panicked at {}:{line}:", file!())),
+            "{error}"
+        );
+        assert!(error.ends_with(": oops"), "{error}");
+        assert_eq!(catch(|| 42), Ok(42));
+    }
+
+    #[test]
+    fn nested_panics() {
+        let _quiet = QuietPanics::install();
+        let error = catch(|| {
+            (0..4)
+                .into_par_iter()
+                .for_each(|number| assert!(number < 3, "{number} is too big"))
+        })
+        .unwrap_err();
+        assert!(error.ends_with("3 is too big"), "{error}");
+    }
+
+    #[test]
+    fn same_messages() {
+        let _quiet = QuietPanics::install();
+        let first = line!() + 1;
+        let panic_first = || panic!("same");
+        let second = line!() + 1;
+        let panic_second = || panic!("same");
+        let errors: Vec<(u32, String)> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..8)
+                .map(|worker| {
+                    scope.spawn(move || match worker % 2 {
+                        0 => (first, catch(panic_first).unwrap_err()),
+                        _ => (second, catch(panic_second).unwrap_err()),
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap())
+                .collect()
+        });
+        for (line, error) in errors {
+            assert!(
+                error.starts_with(&format!("panicked at {}:{line}:", file!())),
+                "{error}"
+            );
+        }
+    }
+}
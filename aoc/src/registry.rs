@@ -0,0 +1,110 @@
+use aoc_common::{Day, Year};
+
+const DAYS_2022: &[Day] = &[
+    day_2022_01::DAY,
+    day_2022_02::DAY,
+    day_2022_03::DAY,
+    day_2022_04::DAY,
+    day_2022_05::DAY,
+    day_2022_06::DAY,
+    day_2022_07::DAY,
+    day_2022_08::DAY,
+    day_2022_09::DAY,
+    day_2022_10::DAY,
+    day_2022_11::DAY,
+    day_2022_12::DAY,
+    day_2022_13::DAY,
+    day_2022_14::DAY,
+    day_2022_15::DAY,
+    day_2022_16::DAY,
+    day_2022_17::DAY,
+];
+
+/// Every event with registered days.
+pub const YEARS: &[Year] = &[
+    // oldest first, `aoc new` keeps them sorted
+    Year::new(2022, DAYS_2022),
+];
+
+pub fn find_year(year: u16) -> Option<&'static Year> {
+    YEARS.iter().find(|registered| registered.year == year)
+}
+
+/// The year that commands use unless they are given one.
+pub fn latest() -> &'static Year {
+    YEARS.last().expect("at least one year is registered")
+}
+
+/// The days of all years, oldest first.
+pub fn all_days() -> impl Iterator<Item = (u16, &'static Day)> {
+    YEARS
+        .iter()
+        .flat_map(|year| year.days.iter().map(|day| (year.year, day)))
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::{unverified_examples, verify_examples, Answer, Parameters, Verdict};
+
+    use super::*;
+
+    #[test]
+    fn sorted() {
+        assert!(YEARS.windows(2).all(|pair| pair[0].year < pair[1].year));
+        for year in YEARS {
+            let days = year.days;
+            assert!(days.windows(2).all(|pair| pair[0].day < pair[1].day));
+        }
+    }
+
+    #[test]
+    fn examples() {
+        let mut failures = Vec::new();
+        for (year, day) in all_days() {
+            for verification in verify_examples(day).unwrap() {
+                if !matches!(verification.verdict, Verdict::Correct) {
+                    failures.push(format!(
+                        "{year} day {} {} part {}: {:?}",
+                        day.day,
+                        verification.input,
+                        verification.part.number(),
+                        verification.verdict
+                    ));
+                }
+            }
+            for example in unverified_examples(day).unwrap() {
+                failures.push(format!(
+                    "{year} day {} {example}: no known answers",
+                    day.day
+                ));
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn parameters() {
+        for (year, day) in all_days() {
+            for parameter in day.parameters {
+                for value in Some(parameter.default).into_iter().chain(parameter.example) {
+                    let mut parameters = Parameters::default();
+                    parameters.set(parameter.name, value);
+                    day.parse_with(day.example, None, &parameters)
+                        .unwrap_or_else(|error| panic!("{year} day {}: {error:?}", day.day));
+                }
+            }
+        }
+        let day_15 = find_year(2022).unwrap().find(15).unwrap();
+        let mut parameters = Parameters::default();
+        parameters.set("row", "-100");
+        let solution = day_15
+            .parse_with(day_15.example, None, &parameters)
+            .unwrap();
+        assert_eq!(solution.part_1().unwrap(), Answer::from(0));
+
+        let mut parameters = Parameters::default();
+        parameters.set("rows", "10");
+        let error = day_15.parse_with("", None, &parameters);
+        assert!(error.is_err());
+    }
+}
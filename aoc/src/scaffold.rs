@@ -0,0 +1,294 @@
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use eyre::{bail, eyre, Context, Result};
+
+/// A day crate to be created by `aoc new`.
+pub struct Scaffold {
+    pub year: u16,
+    pub day: u8,
+    pub title: String,
+}
+
+impl Scaffold {
+    pub fn new(year: u16, day: u8, title: Option<String>) -> Result<Self> {
+        if !(1..=25).contains(&day) {
+            bail!("there is no day {day}, days go from 1 to 25");
+        }
+        if year < 2015 {
+            bail!("there is no Advent of Code {year}, the first one was in 2015");
+        }
+        Ok(Self {
+            year,
+            day,
+            title: title.unwrap_or_else(|| format!("Day {day}")),
+        })
+    }
+
+    /// The year is part of the name, as package names have to be unique in the workspace.
+    fn crate_name(&self) -> String {
+        format!("day-{}-{:02}", self.year, self.day)
+    }
+
+    fn module_name(&self) -> String {
+        self.crate_name().replace('-', "_")
+    }
+
+    /// The solution's type name, the title in upper camel case like `CalorieCounting`.
+    fn type_name(&self) -> String {
+        let name: String = self
+            .title
+            .split(|symbol: char| !symbol.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| word[..1].to_ascii_uppercase() + &word[1..])
+            .collect();
+        match name.chars().next() {
+            Some(first) if first.is_ascii_alphabetic() => name,
+            _ => format!("Day{name}"),
+        }
+    }
+
+    /// Path of the crate relative to the workspace root.
+    pub fn directory(&self) -> PathBuf {
+        Path::new(&self.year.to_string()).join(format!("day-{:02}", self.day))
+    }
+
+    fn render(&self, template: &str) -> String {
+        template
+            .replace("{crate}", &self.crate_name())
+            .replace("{module}", &self.module_name())
+            .replace("{name}", &self.type_name())
+            .replace("{day}", &self.day.to_string())
+            .replace("{title}", &self.title.replace('"', "\\\""))
+    }
+
+    /// Creates the crate and registers it with the workspace and the runner.
+    pub fn create(&self, root: &Path) -> Result<()> {
+        let directory = root.join(self.directory());
+        if directory.exists() {
+            bail!("{} already exists", directory.display());
+        }
+
+        let crate_name = self.crate_name();
+        let dependency = format!(
+            "{crate_name} = {{ path = \"{}\" }}",
+            self.directory().display()
+        );
+        let registration = format!("{crate_name}.workspace = true");
+        // edit everything in memory first, so that a failure leaves the workspace untouched
+        let edit = |path: PathBuf, edit: &dyn Fn(&str) -> Result<String>| {
+            let text = read_to_string(&path)
+                .wrap_err_with(|| format!("failed to read {}", path.display()))?;
+            let text = edit(&text)
+                .wrap_err_with(|| format!("failed to register the day in {}", path.display()))?;
+            Ok::<_, eyre::Report>((path, text))
+        };
+        let edits = [
+            edit(root.join("Cargo.toml"), &|text| {
+                insert_sorted(text, "day-", &dependency)
+            })?,
+            edit(root.join("aoc/Cargo.toml"), &|text| {
+                insert_sorted(text, "day-", &registration)
+            })?,
+            edit(root.join("aoc/src/registry.rs"), &|text| {
+                register_day(text, self.year, &self.module_name())
+            })?,
+        ];
+
+        let files = [
+            (
+                "Cargo.toml",
+                include_str!("../templates/Cargo.toml.template"),
+            ),
+            ("src/main.rs", include_str!("../templates/main.rs.template")),
+            ("src/lib.rs", include_str!("../templates/lib.rs.template")),
+            ("example", ""),
+            ("answers", include_str!("../templates/answers.template")),
+        ];
+        create_dir_all(directory.join("src"))
+            .wrap_err_with(|| format!("failed to create {}", directory.display()))?;
+        for (name, template) in files {
+            let path = directory.join(name);
+            write(&path, self.render(template))
+                .wrap_err_with(|| format!("failed to write {}", path.display()))?;
+        }
+        for (path, text) in edits {
+            write(&path, text).wrap_err_with(|| format!("failed to write {}", path.display()))?;
+        }
+
+        // long titles push the `DAY` declaration over the line width
+        let formatted = Command::new("rustfmt")
+            .args(["--edition", "2021"])
+            .arg(directory.join("src/lib.rs"))
+            .status();
+        if !formatted.is_ok_and(|status| status.success()) {
+            eprintln!("note: could not run rustfmt on the new crate");
+        }
+        Ok(())
+    }
+}
+
+/// Adds the day to the registry, starting a list of days for its year if it is the first.
+fn register_day(registry: &str, year: u16, module: &str) -> Result<String> {
+    let entry = format!("    {module}::DAY,");
+    let prefix = format!("    day_{year}_");
+    if registry.lines().any(|line| line.starts_with(&prefix)) {
+        return insert_sorted(registry, &prefix, &entry);
+    }
+
+    let years = registry
+        .find("/// Every event")
+        .ok_or_else(|| eyre!("found no list of years"))?;
+    let registry = format!(
+        "{}const DAYS_{year}: &[Day] = &[\n{entry}\n];\n\n{}",
+        &registry[..years],
+        &registry[years..]
+    );
+    insert_sorted(
+        &registry,
+        "    Year::new(",
+        &format!("    Year::new({year}, DAYS_{year}),"),
+    )
+}
+
+/// Inserts `line` into the sorted run of lines starting with `prefix`.
+fn insert_sorted(text: &str, prefix: &str, line: &str) -> Result<String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let existing: Vec<usize> = (0..lines.len())
+        .filter(|&index| lines[index].starts_with(prefix))
+        .collect();
+    let (&first, &last) = existing
+        .first()
+        .zip(existing.last())
+        .ok_or_else(|| eyre!("found no lines starting with '{}'", prefix.trim()))?;
+
+    if existing.iter().any(|&index| lines[index] == line) {
+        bail!("'{}' is already there", line.trim());
+    }
+    let position = existing
+        .iter()
+        .rev()
+        .find(|&&index| lines[index] < line)
+        .map_or(first, |index| index + 1)
+        .min(last + 1);
+    lines.insert(position, line);
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, env::temp_dir, fs::remove_dir_all, process};
+
+    use aoc_common::{KnownAnswers, Part};
+
+    use super::*;
+
+    #[test]
+    fn names() {
+        let scaffold = Scaffold::new(2022, 18, Some("Boiling Boulders".to_string())).unwrap();
+        assert_eq!(scaffold.type_name(), "BoilingBoulders");
+        assert_eq!(scaffold.module_name(), "day_2022_18");
+        assert_eq!(scaffold.directory(), Path::new("2022/day-18"));
+
+        let scaffold = Scaffold::new(2022, 3, None).unwrap();
+        assert_eq!(scaffold.type_name(), "Day3");
+        assert!(Scaffold::new(2022, 26, None).is_err());
+        assert!(Scaffold::new(2014, 1, None).is_err());
+    }
+
+    #[test]
+    fn answers_stub() {
+        let scaffold = Scaffold::new(2022, 18, None).unwrap();
+        let stub = scaffold.render(include_str!("../templates/answers.template"));
+        assert!(stub.contains("aoc run 18 --example --save"));
+        let known = KnownAnswers::parse(&stub).unwrap();
+        assert_eq!(known.get("example", Part::One), Some("0"));
+    }
+
+    #[test]
+    fn registration() {
+        let text = "[dependencies]\naoc-common.workspace = true\nday-01.workspace = true\nday-03.workspace = true\neyre.workspace = true\n";
+        assert_eq!(
+            insert_sorted(text, "day-", "day-02.workspace = true").unwrap(),
+            "[dependencies]\naoc-common.workspace = true\nday-01.workspace = true\nday-02.workspace = true\nday-03.workspace = true\neyre.workspace = true\n"
+        );
+        assert!(insert_sorted(text, "day-", "day-04.workspace = true")
+            .unwrap()
+            .contains("day-03.workspace = true\nday-04.workspace = true\neyre"));
+        assert!(insert_sorted(text, "day-", "day-01.workspace = true").is_err());
+    }
+
+    #[test]
+    fn years() {
+        let registry = "const DAYS_2022: &[Day] = &[\n    day_2022_01::DAY,\n];\n\n/// Every event with registered days.\npub const YEARS: &[Year] = &[\n    // oldest first\n    Year::new(2022, DAYS_2022),\n];\n";
+        let registry = register_day(registry, 2022, "day_2022_02").unwrap();
+        assert!(registry.contains("    day_2022_01::DAY,\n    day_2022_02::DAY,\n];"));
+
+        let registry = register_day(&registry, 2021, "day_2021_05").unwrap();
+        assert!(registry.contains(
+            "];\n\nconst DAYS_2021: &[Day] = &[\n    day_2021_05::DAY,\n];\n\n/// Every"
+        ));
+        assert!(registry.contains(
+            "    // oldest first\n    Year::new(2021, DAYS_2021),\n    Year::new(2022, DAYS_2022),\n];"
+        ));
+    }
+
+    #[test]
+    #[ignore = "runs a nested cargo test on a scaffolded day, which takes a while"]
+    fn builds() {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let lockfile = workspace.join("Cargo.lock");
+        if !lockfile.is_file() {
+            eprintln!(
+                "skipping: {} is needed to build the day offline, run `cargo generate-lockfile`",
+                lockfile.display()
+            );
+            return;
+        }
+        let root = temp_dir().join(format!("aoc-scaffold-{}", process::id()));
+        create_dir_all(root.join("aoc/src")).unwrap();
+        // a workspace with one registered day, so that there are lists to insert into
+        let manifest = format!(
+            "[workspace]\nresolver = \"2\"\nmembers = [\"20*/day-*\"]\n\n\
+             [workspace.package]\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+             [workspace.dependencies]\naoc-common = {{ path = {:?} }}\n\
+             day-2022-01 = {{ path = {:?} }}\neyre = \"0.6.12\"\n",
+            workspace.join("common"),
+            workspace.join("2022/day-01"),
+        );
+        write(root.join("Cargo.toml"), manifest).unwrap();
+        write(
+            root.join("aoc/Cargo.toml"),
+            "[dependencies]\nday-2022-01.workspace = true\n",
+        )
+        .unwrap();
+        write(
+            root.join("aoc/src/registry.rs"),
+            read_to_string(workspace.join("aoc/src/registry.rs")).unwrap(),
+        )
+        .unwrap();
+        // pins the dependencies to the versions that are already downloaded
+        write(root.join("Cargo.lock"), read_to_string(&lockfile).unwrap()).unwrap();
+
+        let scaffold = Scaffold::new(2023, 1, Some("Trebuchet?!".to_string())).unwrap();
+        scaffold.create(&root).unwrap();
+        let output = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+            .args(["test", "--offline", "--manifest-path"])
+            .arg(root.join("Cargo.toml"))
+            .env("CARGO_TARGET_DIR", workspace.join("target/scaffold"))
+            .output()
+            .unwrap();
+        remove_dir_all(&root).unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use std::{
+    fmt::Write,
+    sync::mpsc::{channel, Receiver, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use aoc_common::{
+    BenchHistory, Compact, Day, InputSource, KnownAnswers, Parameters, Part, Recorder, Year,
+};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use eyre::{eyre, Result};
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Cell, Paragraph, Row, Table, TableState},
+    DefaultTerminal, Frame,
+};
+
+use crate::panics::{catch, QuietPanics};
+
+/// What the dashboard shows about a day besides its title.
+struct DayStatus {
+    day: &'static Day,
+    /// Whether the answers file has an accepted answer for the puzzle input.
+    stars: [bool; 2],
+    last_answer: Option<String>,
+    last_runtime: Option<Duration>,
+}
+
+impl DayStatus {
+    /// Starts from the recorded answers and the latest benchmark of the puzzle input.
+    fn load(day: &'static Day) -> Self {
+        let known = KnownAnswers::load(day).unwrap_or_default();
+        let history = BenchHistory::load(day).unwrap_or_default();
+        let input = InputSource::Default.name(day).unwrap_or_default();
+        let last_part = Part::ALL
+            .into_iter()
+            .rev()
+            .find(|part| known.get(&input, *part).is_some());
+        Self {
+            day,
+            stars: Part::ALL.map(|part| known.get(&input, part).is_some()),
+            last_answer: last_part.and_then(|part| known.get(&input, part).map(String::from)),
+            last_runtime: last_part.and_then(|part| {
+                let label = format!("part {}", part.number());
+                history.previous(&input, &label).map(|entry| entry.median)
+            }),
+        }
+    }
+}
+
+/// A run of one or both parts of a day, done on a worker thread.
+struct Job {
+    day: &'static Day,
+    parts: Vec<Part>,
+    source: InputSource,
+    /// Show the final frame of the simulation instead of timing the parts.
+    render: bool,
+}
+
+/// The text for the output pane, and the last part's answer and runtime if it was solved.
+struct Outcome {
+    day: u8,
+    output: String,
+    solved: Option<(String, Duration)>,
+}
+
+impl Job {
+    fn run(&self) -> Outcome {
+        let input = match self.source {
+            InputSource::Example => "example input",
+            _ => "puzzle input",
+        };
+        let mut output = format!("Day {}: {}, {input}\n", self.day.day, self.day.title);
+        // a panicking solver should not leave the dashboard waiting for it
+        let solved = catch(|| self.solve(&mut output))
+            .unwrap_or_else(|panic| Err(eyre!(panic)))
+            .unwrap_or_else(|error| {
+                let _ = write!(output, "\n{error:?}");
+                None
+            });
+        Outcome {
+            day: self.day.day,
+            output,
+            solved,
+        }
+    }
+
+    fn solve(&self, output: &mut String) -> Result<Option<(String, Duration)>> {
+        let input = self.source.read(self.day)?;
+        let name = self.source.name(self.day);
+        let start = Instant::now();
+        let solution = self
+            .day
+            .parse_with(&input, name.as_deref(), &Parameters::default())?;
+        writeln!(output, "Parsed in {}", Compact(start.elapsed()))?;
+
+        let mut solved = None;
+        for &part in &self.parts {
+            if self.render {
+                let mut recorder = Recorder::last_frame();
+                let answer = solution.visualize(part, &mut recorder)?;
+                writeln!(output, "\n{part}: {answer}\n")?;
+                for frame in recorder.into_frames() {
+                    output.push_str(&frame.text);
+                }
+                continue;
+            }
+            let start = Instant::now();
+            let answer = solution.solve(part)?;
+            let runtime = start.elapsed();
+            if answer.is_multiline() {
+                writeln!(output, "\n{part} in {}:\n{answer}", Compact(runtime))?;
+            } else {
+                writeln!(output, "\n{part} in {}: {answer}", Compact(runtime))?;
+            }
+            solved = Some((answer.to_string(), runtime));
+        }
+        Ok(solved)
+    }
+}
+
+struct App {
+    year: u16,
+    days: Vec<DayStatus>,
+    table: TableState,
+    example: bool,
+    /// The part that `v` renders, the one run last.
+    part: Part,
+    output: String,
+    scroll: (u16, u16),
+    running: Option<Receiver<Outcome>>,
+}
+
+impl App {
+    fn new(year: &'static Year) -> Self {
+        Self {
+            year: year.year,
+            days: year.days.iter().map(DayStatus::load).collect(),
+            table: TableState::default().with_selected(0),
+            example: false,
+            part: Part::One,
+            output: "Select a day and press 1 or 2 to run a part.".to_string(),
+            scroll: (0, 0),
+            running: None,
+        }
+    }
+
+    fn selected(&self) -> &'static Day {
+        self.days[self.table.selected().unwrap_or(0)].day
+    }
+
+    fn start(&mut self, parts: Vec<Part>, render: bool) {
+        if self.running.is_some() {
+            return;
+        }
+        let job = Job {
+            day: self.selected(),
+            parts,
+            source: if self.example {
+                InputSource::Example
+            } else {
+                InputSource::Default
+            },
+            render,
+        };
+        self.output = format!("Running day {}...", job.day.day);
+        self.scroll = (0, 0);
+        let (sender, receiver) = channel();
+        thread::spawn(move || sender.send(job.run()));
+        self.running = Some(receiver);
+    }
+
+    /// Picks up the outcome of the running job once it is done.
+    fn poll(&mut self) {
+        let Some(running) = &self.running else {
+            return;
+        };
+        let outcome = match running.try_recv() {
+            Ok(outcome) => outcome,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                self.running = None;
+                self.output += "\nThe run stopped without a result.";
+                return;
+            }
+        };
+        self.running = None;
+        self.output = outcome.output;
+        if let Some((answer, runtime)) = outcome.solved {
+            let status = self
+                .days
+                .iter_mut()
+                .find(|status| status.day.day == outcome.day);
+            if let Some(status) = status {
+                status.last_answer = Some(answer);
+                status.last_runtime = Some(runtime);
+            }
+        }
+    }
+
+    /// Handles a key press and returns whether the dashboard should close.
+    fn handle_key(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::Up | KeyCode::Char('k') => self.table.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.table.select_next(),
+            KeyCode::Char('1') => {
+                self.part = Part::One;
+                self.start(vec![Part::One], false);
+            }
+            KeyCode::Char('2') => {
+                self.part = Part::Two;
+                self.start(vec![Part::Two], false);
+            }
+            KeyCode::Char('a') | KeyCode::Enter => self.start(Part::ALL.to_vec(), false),
+            KeyCode::Char('v') => self.start(vec![self.part], true),
+            KeyCode::Char('e') => self.example = !self.example,
+            KeyCode::PageUp => self.scroll.0 = self.scroll.0.saturating_sub(10),
+            KeyCode::PageDown => self.scroll.0 = self.scroll.0.saturating_add(10),
+            KeyCode::Left => self.scroll.1 = self.scroll.1.saturating_sub(10),
+            KeyCode::Right => self.scroll.1 = self.scroll.1.saturating_add(10),
+            KeyCode::Home => self.scroll = (0, 0),
+            _ => {}
+        }
+        self.table.select(Some(
+            self.table.selected().unwrap_or(0).min(self.days.len() - 1),
+        ));
+        false
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, help] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [days, output] =
+            Layout::horizontal([Constraint::Length(64), Constraint::Min(0)]).areas(main);
+
+        let rows = self.days.iter().map(|status| {
+            let stars: String = status
+                .stars
+                .iter()
+                .map(|star| if *star { '*' } else { '.' })
+                .collect();
+            let answer = status.last_answer.as_deref().map_or("", |answer| {
+                if answer.contains('\n') {
+                    "(lines)"
+                } else {
+                    answer
+                }
+            });
+            let runtime = status
+                .last_runtime
+                .map_or(String::new(), |runtime| Compact(runtime).to_string());
+            Row::new([
+                Cell::from(format!("{:2}", status.day.day)),
+                Cell::from(status.day.title),
+                Cell::from(stars),
+                Cell::from(answer.to_string()),
+                Cell::from(Line::from(runtime).right_aligned()),
+            ])
+        });
+        let widths = [
+            Constraint::Length(2),
+            Constraint::Length(24),
+            Constraint::Length(2),
+            Constraint::Length(16),
+            Constraint::Length(8),
+        ];
+        let table = Table::new(rows, widths)
+            .header(Row::new(["", "Title", "", "Last answer", "Runtime"]))
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .block(Block::bordered().title(format!(" Advent of Code {} ", self.year)));
+        frame.render_stateful_widget(table, days, &mut self.table);
+
+        let title = if self.example {
+            " Output, example input "
+        } else {
+            " Output, puzzle input "
+        };
+        let paragraph = Paragraph::new(self.output.as_str())
+            .scroll(self.scroll)
+            .block(Block::bordered().title(title));
+        frame.render_widget(paragraph, output);
+
+        frame.render_widget(
+            Line::from(
+                " q quit  ↑↓ day  1/2 run part  a both  v render  e example/puzzle input  \
+                 PgUp/PgDn/←→ scroll",
+            ),
+            help,
+        );
+    }
+}
+
+/// Shows the dashboard for the days of `year` until the user quits.
+pub fn run(year: &'static Year) -> Result<()> {
+    let mut terminal = ratatui::init();
+    // solvers panic on worker threads, where the message would be printed over the dashboard
+    let quiet = QuietPanics::install();
+    let result = event_loop(&mut terminal, year);
+    drop(quiet);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, year: &'static Year) -> Result<()> {
+    let mut app = App::new(year);
+    loop {
+        app.poll();
+        terminal.draw(|frame| app.draw(frame))?;
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && app.handle_key(key.code) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn runs_a_day() {
+        let job = Job {
+            day: registry::find_year(2022).unwrap().find(10).unwrap(),
+            parts: Part::ALL.to_vec(),
+            source: InputSource::Example,
+            render: false,
+        };
+        let outcome = job.run();
+        assert!(outcome.output.contains("##..##..##"), "{}", outcome.output);
+        assert!(outcome.solved.is_some());
+
+        let job = Job {
+            day: registry::find_year(2022).unwrap().find(17).unwrap(),
+            parts: vec![Part::One],
+            render: true,
+            ..job
+        };
+        let chamber = job.run().output;
+        assert!(chamber.contains("Part 1: 3068"), "{chamber}");
+        assert!(chamber
+            .lines()
+            .any(|row| row.starts_with('|') && row.contains('#')));
+
+        let job = Job {
+            day: registry::find_year(2022).unwrap().find(14).unwrap(),
+            parts: vec![Part::Two],
+            ..job
+        };
+        let cave = job.run().output;
+        assert!(cave.contains("Part 2: 93"), "{cave}");
+        assert!(cave.contains("\n..ooooo.......ooooooooo..\n###"), "{cave}");
+    }
+
+    #[test]
+    fn draws_all_days() {
+        let year = registry::find_year(2022).unwrap();
+        let mut app = App::new(year);
+        app.handle_key(KeyCode::Down);
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Advent of Code 2022"));
+        for day in year.days {
+            assert!(screen.contains(day.title), "{}", day.title);
+        }
+        assert_eq!(app.selected().day, 2);
+    }
+
+    #[test]
+    fn lost_jobs() {
+        let mut app = App::new(registry::find_year(2022).unwrap());
+        let (sender, receiver) = channel();
+        app.running = Some(receiver);
+        drop(sender);
+        app.poll();
+        assert!(app.running.is_none());
+        assert!(app.output.ends_with("without a result."), "{}", app.output);
+    }
+}
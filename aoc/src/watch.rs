@@ -0,0 +1,293 @@
+use std::{
+    collections::BTreeMap,
+    fs::{metadata, read_dir},
+    path::{absolute, Path, PathBuf},
+    process::{Command, Output},
+    thread::sleep,
+    time::{Duration, Instant, SystemTime},
+};
+
+use aoc_common::{known_examples, Compact, Day, InputSource};
+use eyre::{bail, Context, Result};
+use serde::Deserialize;
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How long the files have to stay unchanged before a run starts, as editors and `git
+/// checkout` write several files in a row.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Answers by input and part.
+type Answers = BTreeMap<(String, u8), String>;
+
+/// A line of `aoc run --format json`, of which only the answer matters here.
+#[derive(Deserialize)]
+struct JsonAnswer {
+    input: Option<String>,
+    part: u8,
+    answer: String,
+}
+
+/// Rebuilds and re-runs a day whenever its sources, the shared library, its input or examples
+/// change, until killed.
+///
+/// Every run builds the runner, runs the tests of the day's crate and solves the examples with
+/// known answers and the selected input, then prints how the answers differ from the previous
+/// run. Only the examples are solved for [`InputSource::Example`].
+pub fn watch(root: &Path, year: u16, day: &Day, source: &InputSource) -> Result<()> {
+    let input = match source {
+        InputSource::Default => Some(day.input_path()),
+        InputSource::Path(path) => {
+            Some(absolute(path).wrap_err_with(|| format!("failed to locate {}", path.display()))?)
+        }
+        InputSource::Example => None,
+        InputSource::Stdin => bail!("stdin cannot be read again after a change, pass a file"),
+    };
+    let watched = Watched {
+        root,
+        day,
+        input: input.as_deref(),
+    };
+    let mut files = watched.modification_times();
+    let mut previous = Answers::new();
+    loop {
+        println!("\nday {} of {year}: building", day.day);
+        let start = Instant::now();
+        if let Some(answers) = run(root, year, day, input.as_deref())? {
+            let changes = changes(&previous, &answers);
+            if changes.is_empty() {
+                println!("answers unchanged");
+            }
+            for change in changes {
+                println!("{change}");
+            }
+            previous = answers;
+        }
+        println!(
+            "done in {}, watching {} and {}",
+            Compact(start.elapsed()),
+            day.directory,
+            root.join("common").display()
+        );
+
+        files = watched.wait_for_change(files);
+    }
+}
+
+/// The inputs to solve after a change: the bundled example, the other examples with known
+/// answers like `aoc verify` checks them, and the selected input if it exists.
+fn inputs(day: &Day, input: Option<&Path>) -> Result<Vec<PathBuf>> {
+    let directory = Path::new(day.directory);
+    let mut examples = known_examples(day)?;
+    if !day.example.is_empty() && !examples.iter().any(|example| example == "example") {
+        examples.insert(0, "example".to_string());
+    }
+    let mut inputs: Vec<PathBuf> = examples
+        .iter()
+        .map(|example| directory.join(example))
+        .collect();
+    inputs.extend(input.filter(|input| input.is_file()).map(Path::to_path_buf));
+    Ok(inputs)
+}
+
+/// Builds and runs everything once; `None` if the build failed, which has been reported.
+fn run(root: &Path, year: u16, day: &Day, input: Option<&Path>) -> Result<Option<Answers>> {
+    let build = cargo(root, &["build", "--quiet", "--package", "aoc"])?;
+    if !build.status.success() {
+        print!("{}", String::from_utf8_lossy(&build.stderr));
+        return Ok(None);
+    }
+
+    let package = format!("day-{year}-{:02}", day.day);
+    let tests = cargo(root, &["test", "--quiet", "--package", &package])?;
+    if tests.status.success() {
+        println!("tests passed");
+    } else {
+        print!("{}", String::from_utf8_lossy(&tests.stdout));
+        print!("{}", String::from_utf8_lossy(&tests.stderr));
+    }
+
+    let mut answers = Answers::new();
+    let day_number = day.day.to_string();
+    let year = year.to_string();
+    for path in inputs(day, input)? {
+        let path_argument = path.to_string_lossy();
+        let mut command = vec!["run", "--quiet", "--package", "aoc", "--", "run"];
+        command.extend(["--year", &year, &day_number, "--format", "json"]);
+        command.extend(["--input", &path_argument]);
+        let output = cargo(root, &command)?;
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let answer: JsonAnswer = serde_json::from_str(line)
+                .wrap_err_with(|| format!("unexpected output of the runner: {line}"))?;
+            // inputs outside the day's directory have no name
+            let input = answer.input.unwrap_or_else(|| path.display().to_string());
+            answers.insert((input, answer.part), answer.answer);
+        }
+        // warnings about known answers and errors, the runner shows no diagnostics by default
+        print!("{}", String::from_utf8_lossy(&output.stderr));
+    }
+    Ok(Some(answers))
+}
+
+fn cargo(root: &Path, arguments: &[&str]) -> Result<Output> {
+    Command::new(env!("CARGO"))
+        .args(arguments)
+        .current_dir(root)
+        .output()
+        .wrap_err_with(|| format!("failed to run cargo {}", arguments.join(" ")))
+}
+
+/// Describes the answers that are new, changed or gone since the previous run.
+///
+/// Multi-line answers start on the next line, like `aoc run` prints them.
+fn changes(previous: &Answers, current: &Answers) -> Vec<String> {
+    let mut changes = Vec::new();
+    for ((input, part), answer) in current {
+        let label = format!("{input} part {part}");
+        let old = previous.get(&(input.clone(), *part));
+        if old == Some(answer) {
+            continue;
+        }
+        changes.push(match old {
+            _ if answer.contains('\n') => format!("{label}:\n{answer}"),
+            Some(old) if !old.contains('\n') => format!("{label}: {answer} (was {old})"),
+            _ => format!("{label}: {answer}"),
+        });
+    }
+    for (input, part) in previous.keys() {
+        if !current.contains_key(&(input.clone(), *part)) {
+            changes.push(format!("{input} part {part}: no answer any more"));
+        }
+    }
+    changes
+}
+
+/// The files that trigger a run.
+struct Watched<'a> {
+    root: &'a Path,
+    day: &'a Day,
+    /// The selected input, unless only the examples are solved.
+    input: Option<&'a Path>,
+}
+
+impl Watched<'_> {
+    /// The sources of the day and of the shared library, the examples and the selected input.
+    fn modification_times(&self) -> BTreeMap<PathBuf, SystemTime> {
+        let directory = Path::new(self.day.directory);
+        let mut files = BTreeMap::new();
+        let mut pending = vec![directory.join("src"), self.root.join("common/src")];
+        if let Ok(entries) = read_dir(directory) {
+            pending.extend(entries.flatten().map(|entry| entry.path()).filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("example"))
+            }));
+        }
+        pending.extend(self.input.map(Path::to_path_buf));
+        while let Some(path) = pending.pop() {
+            let Ok(metadata) = metadata(&path) else {
+                continue;
+            };
+            if metadata.is_dir() {
+                if let Ok(entries) = read_dir(&path) {
+                    pending.extend(entries.flatten().map(|entry| entry.path()));
+                }
+            } else if let Ok(modified) = metadata.modified() {
+                files.insert(path, modified);
+            }
+        }
+        files
+    }
+
+    /// Waits until the files change and then stay unchanged for [`DEBOUNCE`].
+    fn wait_for_change(
+        &self,
+        files: BTreeMap<PathBuf, SystemTime>,
+    ) -> BTreeMap<PathBuf, SystemTime> {
+        let mut current = files.clone();
+        while current == files {
+            sleep(POLL_INTERVAL);
+            current = self.modification_times();
+        }
+        let mut settled = Instant::now();
+        while settled.elapsed() < DEBOUNCE {
+            sleep(POLL_INTERVAL);
+            let latest = self.modification_times();
+            if latest != current {
+                current = latest;
+                settled = Instant::now();
+            }
+        }
+        current
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn only_changes_are_shown() {
+        let answer = |input: &str, part, answer: &str| ((input.to_string(), part), answer.into());
+        let previous = Answers::from([
+            answer("example", 1, "1651"),
+            answer("example", 2, "1706"),
+            answer("input", 1, "2000"),
+        ]);
+        let current = Answers::from([
+            answer("example", 1, "1651"),
+            answer("example", 2, "1707"),
+            answer("input", 2, "2400"),
+        ]);
+        assert_eq!(
+            changes(&previous, &current),
+            [
+                "example part 2: 1707 (was 1706)",
+                "input part 2: 2400",
+                "input part 1: no answer any more"
+            ]
+        );
+        assert!(changes(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn watches_sources_and_inputs() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let day = crate::registry::find_year(2022).unwrap().find(6).unwrap();
+        let input = Path::new(day.directory).join("input");
+        let watched = Watched {
+            root,
+            day,
+            input: Some(&input),
+        };
+        let files = watched.modification_times();
+        let names: Vec<_> = files
+            .keys()
+            .filter_map(|path| path.strip_prefix(day.directory).ok())
+            .collect();
+        assert!(names.contains(&Path::new("src/lib.rs")));
+        assert!(names.contains(&Path::new("example2")));
+        assert!(!names.contains(&Path::new("answers")));
+        assert!(files.contains_key(&root.join("common/src/lib.rs")));
+    }
+
+    #[test]
+    fn reruns_known_examples() {
+        let day = crate::registry::find_year(2022).unwrap().find(6).unwrap();
+        let directory = Path::new(day.directory);
+        let names: Vec<_> = inputs(day, Some(Path::new("does-not-exist")))
+            .unwrap()
+            .into_iter()
+            .map(|path| path.strip_prefix(directory).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            names,
+            ["example", "example2", "example3", "example4", "example5"].map(PathBuf::from)
+        );
+
+        let input = directory.join("example3");
+        let paths = inputs(day, Some(&input)).unwrap();
+        assert_eq!(paths.last(), Some(&input));
+    }
+}
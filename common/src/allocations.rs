@@ -0,0 +1,162 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::{self, Display},
+    sync::atomic::{AtomicUsize, Ordering::Relaxed},
+};
+
+/// The system allocator, counting what passes through it.
+///
+/// Installed as the global allocator by the `count-allocations` feature.
+pub struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static IN_USE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+fn record_allocation(size: usize) {
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    ALLOCATED.fetch_add(size, Relaxed);
+    let in_use = IN_USE.fetch_add(size, Relaxed) + size;
+    PEAK.fetch_max(in_use, Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = unsafe { System.alloc(layout) };
+        if !pointer.is_null() {
+            record_allocation(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer = unsafe { System.alloc_zeroed(layout) };
+        if !pointer.is_null() {
+            record_allocation(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(pointer, layout) };
+        IN_USE.fetch_sub(layout.size(), Relaxed);
+    }
+
+    /// Counts as a new allocation of `new_size` bytes, as growing a `Vec` usually copies.
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_pointer = unsafe { System.realloc(pointer, layout, new_size) };
+        if !new_pointer.is_null() {
+            IN_USE.fetch_sub(layout.size(), Relaxed);
+            record_allocation(new_size);
+        }
+        new_pointer
+    }
+}
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// What a step allocated on the heap, see [`measure_allocations`].
+///
+/// `peak` counts from the heap that was already in use when the step started, such as the
+/// parsed input, so it is what the step itself needed at most and does not depend on what ran
+/// before it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Allocations {
+    pub count: usize,
+    pub bytes: usize,
+    /// The most heap in use at once during the step, beyond what was in use when it started.
+    pub peak: usize,
+}
+
+impl Display for Allocations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.count,
+            Bytes(self.bytes),
+            Bytes(self.peak)
+        )
+    }
+}
+
+/// Formats a byte count with a binary unit, like `1.5 MiB`.
+struct Bytes(usize);
+
+impl Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut value = self.0 as f64;
+        for unit in ["B", "KiB", "MiB"] {
+            if value < 1024.0 {
+                return if unit == "B" {
+                    write!(f, "{} {unit}", self.0)
+                } else {
+                    write!(f, "{value:.1} {unit}")
+                };
+            }
+            value /= 1024.0;
+        }
+        write!(f, "{value:.1} GiB")
+    }
+}
+
+/// Runs `step` and returns what it allocated, or `None` without the `count-allocations` feature.
+///
+/// The counters are shared by all threads, so steps measured at the same time see each
+/// other's allocations; threads that `step` starts itself, like rayon's, are included.
+pub fn measure_allocations<T>(step: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    if !cfg!(feature = "count-allocations") {
+        return (step(), None);
+    }
+    let count = ALLOCATIONS.load(Relaxed);
+    let bytes = ALLOCATED.load(Relaxed);
+    let baseline = IN_USE.load(Relaxed);
+    PEAK.store(baseline, Relaxed);
+    let result = step();
+    let allocations = Allocations {
+        count: ALLOCATIONS.load(Relaxed) - count,
+        bytes: ALLOCATED.load(Relaxed) - bytes,
+        // other threads may free heap from before the step, which lowers the peak below it
+        peak: PEAK.load(Relaxed).saturating_sub(baseline),
+    };
+    (result, Some(allocations))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let allocations = Allocations {
+            count: 3,
+            bytes: 1536,
+            peak: 900,
+        };
+        assert_eq!(
+            allocations.to_string(),
+            "3 allocations, 1.5 KiB allocated, 900 B peak"
+        );
+        assert_eq!(Bytes(5 << 30).to_string(), "5.0 GiB");
+    }
+
+    #[cfg(feature = "count-allocations")]
+    #[test]
+    fn counts() {
+        let (vector, allocations) = measure_allocations(|| Vec::<u8>::with_capacity(64 << 20));
+        let allocations = allocations.unwrap();
+        assert!(allocations.count >= 1);
+        assert!(allocations.bytes >= vector.capacity());
+        assert!(allocations.peak >= vector.capacity());
+
+        let (small, allocations) = measure_allocations(|| Vec::<u8>::with_capacity(100));
+        assert!(
+            allocations.unwrap().peak < vector.capacity(),
+            "{allocations:?}"
+        );
+        drop((vector, small));
+    }
+}
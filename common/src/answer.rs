@@ -0,0 +1,73 @@
+use std::fmt::Display;
+
+/// The result of solving one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    /// Multi-line output such as the letters drawn by day 10's CRT.
+    Lines(Vec<String>),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Lines(lines) if lines.len() > 1)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{number}"),
+            Answer::Text(text) => f.write_str(text),
+            Answer::Lines(lines) => f.write_str(&lines.join("\n")),
+        }
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($number:ty),*) => {
+        $(
+            impl From<$number> for Answer {
+                fn from(value: $number) -> Self {
+                    Answer::Number(i64::try_from(value).expect("answer does not fit into an i64"))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(i32, i64, isize, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(lines: Vec<String>) -> Self {
+        Answer::Lines(lines)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::from(24000u32).to_string(), "24000");
+        assert_eq!(Answer::from("CMZ").to_string(), "CMZ");
+
+        let lines = Answer::from(vec!["##..".to_string(), "###.".to_string()]);
+        assert!(lines.is_multiline());
+        assert_eq!(lines.to_string(), "##..\n###.");
+    }
+}
@@ -0,0 +1,184 @@
+use std::{
+    fmt::{self, Display},
+    fs::{read_to_string, write},
+};
+
+use eyre::{Context, Result};
+
+use crate::{
+    labelled, lines, literal, one_of, parse_all, take_while, Cursor, Day, ParseError, Part,
+};
+
+/// Accepted answers of a day, stored in the file `answers` in the day's directory.
+///
+/// Each entry names an input file next to it and a part. Multi-line answers continue on
+/// the following lines, indented by two spaces:
+///
+/// ```text
+/// example part 1: 13140
+/// example part 2:
+///   ##..##..##
+///   ###...###.
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KnownAnswers {
+    entries: Vec<KnownAnswer>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KnownAnswer {
+    /// File name of the input inside the day's directory, e.g. `input` or `example`.
+    pub input: String,
+    pub part: Part,
+    pub answer: String,
+}
+
+const INDENT: &str = "  ";
+
+impl KnownAnswers {
+    /// Reads the answers of a day; a missing file means nothing is known yet.
+    pub fn load(day: &Day) -> Result<Self> {
+        let path = day.answers_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text =
+            read_to_string(&path).wrap_err_with(|| format!("failed to read {}", path.display()))?;
+        Self::parse(&text).wrap_err_with(|| format!("invalid answers in {}", path.display()))
+    }
+
+    pub fn save(&self, day: &Day) -> Result<()> {
+        let path = day.answers_path();
+        write(&path, self.to_string())
+            .wrap_err_with(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut entries: Vec<KnownAnswer> = Vec::new();
+
+        for line in parse_all(text, lines(answer_line))? {
+            match line {
+                Line::Entry(entry) => entries.push(entry),
+                Line::Continuation(continuation) => {
+                    let entry = entries.last_mut().ok_or_else(|| {
+                        ParseError::new(text, continuation, "continuation line without an entry")
+                    })?;
+                    if !entry.answer.is_empty() {
+                        entry.answer.push('\n');
+                    }
+                    entry.answer.push_str(continuation);
+                }
+                Line::Skipped => {}
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    pub fn get(&self, input: &str, part: Part) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|entry| entry.input == input && entry.part == part)
+            .map(|entry| entry.answer.as_str())
+    }
+
+    /// Records an answer, replacing any earlier one for the same input and part.
+    pub fn insert(&mut self, input: &str, part: Part, answer: String) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.input == input && entry.part == part)
+        {
+            Some(entry) => entry.answer = answer,
+            None => self.entries.push(KnownAnswer {
+                input: input.to_string(),
+                part,
+                answer,
+            }),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &KnownAnswer> {
+        self.entries.iter()
+    }
+}
+
+/// A line of the answers file.
+enum Line<'a> {
+    Entry(KnownAnswer),
+    /// The next line of a multi-line answer, without the indentation.
+    Continuation(&'a str),
+    /// A blank line or a comment.
+    Skipped,
+}
+
+fn answer_line<'a>(cursor: &mut Cursor<'a>) -> Result<Line<'a>, ParseError> {
+    if cursor.eat(INDENT) {
+        return Ok(Line::Continuation(cursor.advance(cursor.rest().len())));
+    }
+    if cursor.is_at_end() || cursor.rest().starts_with('#') {
+        cursor.advance(cursor.rest().len());
+        return Ok(Line::Skipped);
+    }
+
+    let input = take_while(|next| next != ':' && !next.is_whitespace())(cursor)?;
+    if input.is_empty() {
+        return Err(cursor.expected("'<input> part <n>: <answer>'"));
+    }
+    let part = labelled(" part ", one_of(&[("1", Part::One), ("2", Part::Two)]))(cursor)?;
+    literal(":")(cursor)?;
+    take_while(char::is_whitespace)(cursor)?;
+    Ok(Line::Entry(KnownAnswer {
+        input: input.to_string(),
+        part,
+        answer: cursor.advance(cursor.rest().len()).to_string(),
+    }))
+}
+
+impl Display for KnownAnswers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            write!(f, "{} part {}:", entry.input, entry.part.number())?;
+            if entry.answer.contains('\n') {
+                for line in entry.answer.lines() {
+                    write!(f, "\n{INDENT}{line}")?;
+                }
+                writeln!(f)?;
+            } else {
+                writeln!(f, " {}", entry.answer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let text = "example part 1: 13140\nexample part 2:\n  ##..\n  ###.\ninput part 1: CMZ\n";
+        let answers = KnownAnswers::parse(text).unwrap();
+
+        assert_eq!(answers.get("example", Part::One), Some("13140"));
+        assert_eq!(answers.get("example", Part::Two), Some("##..\n###."));
+        assert_eq!(answers.get("input", Part::Two), None);
+        assert_eq!(answers.to_string(), text);
+    }
+
+    #[test]
+    fn insert_replaces() {
+        let mut answers = KnownAnswers::default();
+        answers.insert("input", Part::One, "1".to_string());
+        answers.insert("input", Part::One, "2".to_string());
+        assert_eq!(answers.to_string(), "input part 1: 2\n");
+
+        let error = KnownAnswers::parse("input part 3: 1\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 12));
+        let error = KnownAnswers::parse("# comment\ninput part 1 2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 13));
+        let error = KnownAnswers::parse("\n  ##..\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+}
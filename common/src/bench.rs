@@ -0,0 +1,252 @@
+use std::{
+    fmt::{self, Display},
+    fs::{read_to_string, OpenOptions},
+    io::Write,
+    time::{Duration, Instant},
+};
+
+use eyre::{Context, Result};
+
+use crate::{
+    labelled, lines, literal, parse_all, take_while, unix_time, unsigned, Cursor, Day, InputSource,
+    Parameters, ParseError, Part,
+};
+
+/// Durations of repeated runs of the same step.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timing {
+    /// Sorted from fastest to slowest.
+    samples: Vec<Duration>,
+}
+
+impl Timing {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "a timing needs at least one sample");
+        samples.sort();
+        Self { samples }
+    }
+
+    /// Runs `f` the given number of times and records how long each run took.
+    pub fn measure<T>(iterations: usize, mut f: impl FnMut() -> Result<T>) -> Result<Self> {
+        let mut samples = Vec::with_capacity(iterations);
+        for _ in 0..iterations.max(1) {
+            let start = Instant::now();
+            std::hint::black_box(f()?);
+            samples.push(start.elapsed());
+        }
+        Ok(Self::from_samples(samples))
+    }
+
+    pub fn median(&self) -> Duration {
+        let middle = self.samples.len() / 2;
+        if self.samples.len().is_multiple_of(2) {
+            (self.samples[middle - 1] + self.samples[middle]) / 2
+        } else {
+            self.samples[middle]
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples[0]
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples[self.samples.len() - 1]
+    }
+}
+
+/// The timing of one step of a day, e.g. `parse` or `part 2 (brute force)`.
+#[derive(Clone, Debug)]
+pub struct Measurement {
+    pub label: String,
+    pub timing: Timing,
+}
+
+/// Times parsing and each part separately, each over the given number of iterations.
+///
+/// Parts are timed on a single parsed solution. With `variants`, the alternative
+/// algorithms of the requested parts are timed as well.
+pub fn bench(
+    day: &Day,
+    source: &InputSource,
+    parts: &[Part],
+    parameters: &Parameters,
+    iterations: usize,
+    variants: bool,
+) -> Result<Vec<Measurement>> {
+    let input = source.read(day)?;
+    let name = source.name(day);
+    let parse = || day.parse_with(&input, name.as_deref(), parameters);
+    let solution = parse()?;
+
+    let mut measurements = vec![Measurement {
+        label: "parse".to_string(),
+        timing: Timing::measure(iterations, parse)?,
+    }];
+    for &part in parts {
+        measurements.push(Measurement {
+            label: format!("part {}", part.number()),
+            timing: Timing::measure(iterations, || solution.solve(part))?,
+        });
+        if !variants {
+            continue;
+        }
+        for variant in solution
+            .variants()
+            .iter()
+            .filter(|variant| variant.part == part)
+        {
+            measurements.push(Measurement {
+                label: format!("part {} ({})", part.number(), variant.name),
+                timing: Timing::measure(iterations, || solution.solve_variant(variant.name))?,
+            });
+        }
+    }
+
+    Ok(measurements)
+}
+
+/// Earlier benchmark results of a day, stored in the file `bench-history` in its directory.
+///
+/// Every line holds the Unix time of the run, the input name, the label of the step and
+/// its median, fastest and slowest time in nanoseconds, separated by tabs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BenchHistory {
+    entries: Vec<HistoryEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub timestamp: u64,
+    pub input: String,
+    pub label: String,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl BenchHistory {
+    /// Reads the history of a day; a missing file means there were no earlier runs.
+    pub fn load(day: &Day) -> Result<Self> {
+        let path = day.bench_history_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text =
+            read_to_string(&path).wrap_err_with(|| format!("failed to read {}", path.display()))?;
+        Self::parse(&text).wrap_err_with(|| format!("invalid history in {}", path.display()))
+    }
+
+    /// Parses lines of tab-separated fields: timestamp, input, label and the median, fastest
+    /// and slowest run in nanoseconds.
+    pub fn parse<'a>(text: &'a str) -> Result<Self, ParseError> {
+        let field = |cursor: &mut Cursor<'a>| {
+            let field = labelled("\t", take_while(|next| next != '\t'))(cursor)?;
+            Ok(field.to_string())
+        };
+        let nanos = |cursor: &mut Cursor<'a>| {
+            literal("\t")(cursor)?;
+            unsigned(cursor).map(Duration::from_nanos)
+        };
+        let entry = |cursor: &mut Cursor<'a>| {
+            Ok(HistoryEntry {
+                timestamp: unsigned(cursor)?,
+                input: field(cursor)?,
+                label: field(cursor)?,
+                median: nanos(cursor)?,
+                min: nanos(cursor)?,
+                max: nanos(cursor)?,
+            })
+        };
+
+        Ok(Self {
+            entries: parse_all(text, lines(entry))?,
+        })
+    }
+
+    /// The most recent result for the same step on the same input.
+    pub fn previous(&self, input: &str, label: &str) -> Option<&HistoryEntry> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.input == input && entry.label == label)
+    }
+
+    /// Appends the results of a run to the history file of the day.
+    pub fn append(day: &Day, input: &str, measurements: &[Measurement]) -> Result<()> {
+        let timestamp = unix_time();
+        let mut lines = String::new();
+        for Measurement { label, timing } in measurements {
+            lines.push_str(&format!(
+                "{timestamp}\t{input}\t{label}\t{}\t{}\t{}\n",
+                timing.median().as_nanos(),
+                timing.min().as_nanos(),
+                timing.max().as_nanos()
+            ));
+        }
+
+        let path = day.bench_history_path();
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(lines.as_bytes()))
+            .wrap_err_with(|| format!("failed to write {}", path.display()))
+    }
+}
+
+/// Formats a duration with three significant digits in a fitting unit, e.g. `12.3ms`.
+pub struct Compact(pub Duration);
+
+impl Display for Compact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nanos = self.0.as_nanos() as f64;
+        let (value, unit) = match nanos {
+            n if n < 1e3 => (n, "ns"),
+            n if n < 1e6 => (n / 1e3, "µs"),
+            n if n < 1e9 => (n / 1e6, "ms"),
+            n => (n / 1e9, "s"),
+        };
+        let precision = match value {
+            v if v < 10.0 => 2,
+            v if v < 100.0 => 1,
+            _ => 0,
+        };
+        let text = format!("{value:.precision$}{unit}");
+        f.pad(&text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn timing() {
+        let timing = Timing::from_samples([4, 1, 3, 2].map(Duration::from_millis).to_vec());
+        assert_eq!(timing.median(), Duration::from_micros(2500));
+        assert_eq!(timing.min(), Duration::from_millis(1));
+        assert_eq!(timing.max(), Duration::from_millis(4));
+
+        assert_eq!(Compact(Duration::from_nanos(950)).to_string(), "950ns");
+        assert_eq!(Compact(Duration::from_micros(12_345)).to_string(), "12.3ms");
+        assert_eq!(Compact(Duration::from_millis(2500)).to_string(), "2.50s");
+    }
+
+    #[test]
+    fn history() {
+        let text = "100\tinput\tparse\t30\t20\t40\n200\tinput\tparse\t10\t5\t15\n";
+        let history = BenchHistory::parse(text).unwrap();
+        let previous = history.previous("input", "parse").unwrap();
+        assert_eq!(
+            (previous.timestamp, previous.median),
+            (200, Duration::from_nanos(10))
+        );
+        assert!(history.previous("example", "parse").is_none());
+
+        let error = BenchHistory::parse("100\tinput\tparse\tfast\t5\t15\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 17));
+        let error = BenchHistory::parse("100\tinput\tparse\t30\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 19));
+    }
+}
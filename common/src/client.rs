@@ -0,0 +1,276 @@
+use std::{
+    env,
+    fs::{read_to_string, write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use eyre::{bail, eyre, Context, Result};
+use ureq::Agent;
+
+use crate::{parameters::assignments, Day, ParseError, Part, Reply};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings for talking to the Advent of Code website.
+///
+/// Values come from the environment variables `AOC_SESSION` and `AOC_BASE_URL`, falling back
+/// to `session = ...` and `base_url = ...` lines in `$XDG_CONFIG_HOME/aoc/config` (usually
+/// `~/.config/aoc/config`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// The value of the `session` cookie of a logged in browser.
+    pub session: Option<String>,
+    pub base_url: String,
+}
+
+impl Config {
+    pub fn load() -> Result<Self> {
+        let file = match config_path() {
+            Some(path) if path.is_file() => read_to_string(&path)
+                .wrap_err_with(|| format!("failed to read {}", path.display()))?,
+            _ => String::new(),
+        };
+        let mut config = Self::parse(&file)?;
+
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = base_url;
+        }
+        Ok(config)
+    }
+
+    /// Parses `key = value` lines; blank lines and lines starting with `#` are skipped.
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut config = Self {
+            session: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+        };
+
+        for (key, value) in assignments(text)? {
+            match key {
+                "session" => config.session = Some(value.to_string()),
+                "base_url" => config.base_url = value.to_string(),
+                key => {
+                    return Err(ParseError::new(
+                        text,
+                        key,
+                        format!("unknown config key '{key}'"),
+                    ))
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    let directory = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(directory.join("aoc").join("config"))
+}
+
+/// Downloads puzzle inputs and submits answers with a session token.
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> Result<Self> {
+        let session = config.session.clone().ok_or_else(|| {
+            eyre!("no session token configured, set AOC_SESSION or 'session' in the config")
+        })?;
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .user_agent(concat!("aoc-runner/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .into();
+
+        Ok(Self {
+            agent,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        })
+    }
+
+    /// Fetches the puzzle input of a day from the server.
+    pub fn download_input(&self, year: u16, day: u8) -> Result<String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let mut response = self
+            .agent
+            .get(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .call()
+            .wrap_err_with(|| format!("failed to download {url}"))?;
+
+        let status = response.status();
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .wrap_err_with(|| format!("failed to read the response of {url}"))?;
+        if !status.is_success() {
+            bail!("downloading {url} failed with {status}: {}", body.trim());
+        }
+        Ok(body)
+    }
+
+    /// Posts an answer for a part and reports how the server judged it.
+    pub fn submit_answer(&self, year: u16, day: u8, part: Part, answer: &str) -> Result<Reply> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let level = part.number().to_string();
+        let mut response = self
+            .agent
+            .post(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .send_form([("level", level.as_str()), ("answer", answer)])
+            .wrap_err_with(|| format!("failed to submit to {url}"))?;
+
+        let status = response.status();
+        let page = response
+            .body_mut()
+            .read_to_string()
+            .wrap_err_with(|| format!("failed to read the response of {url}"))?;
+        if !status.is_success() {
+            bail!("submitting to {url} failed with {status}: {}", page.trim());
+        }
+        Reply::parse(&page)
+    }
+
+    /// Returns the input stored at `path`, downloading it only if it is not there yet.
+    pub fn cached_input(&self, year: u16, day: u8, path: &Path) -> Result<String> {
+        if path.is_file() {
+            return read_to_string(path)
+                .wrap_err_with(|| format!("failed to read {}", path.display()));
+        }
+        let input = self.download_input(year, day)?;
+        write(path, &input).wrap_err_with(|| format!("failed to write {}", path.display()))?;
+        Ok(input)
+    }
+
+    /// Makes sure the day's `input` file exists, downloading it if needed.
+    pub fn fetch_input(&self, year: u16, day: &Day) -> Result<String> {
+        self.cached_input(year, day.day, &day.input_path())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env::temp_dir, process, thread};
+
+    use tiny_http::{Response, Server};
+
+    use super::*;
+
+    #[test]
+    fn config() {
+        let config =
+            Config::parse("# token\nsession = abc\n\nbase_url = http://localhost\n").unwrap();
+        assert_eq!(config.session.as_deref(), Some("abc"));
+        assert_eq!(config.base_url, "http://localhost");
+
+        assert_eq!(Config::parse("").unwrap().base_url, DEFAULT_BASE_URL);
+        let error = Config::parse("token abc").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 7));
+        let error = Config::parse("session = abc\nyear = 2022").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn downloads_once() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let handler = thread::spawn(move || {
+            let mut requests = Vec::new();
+            // only the first request is answered, a second download would fail
+            let request = server.recv().unwrap();
+            let cookie = request
+                .headers()
+                .iter()
+                .find(|header| header.field.equiv("Cookie"))
+                .map(|header| header.value.to_string());
+            requests.push((request.url().to_string(), cookie));
+            request.respond(Response::from_string("1\n2\n")).unwrap();
+            requests
+        });
+
+        let client = Client::new(&Config {
+            session: Some("secret".to_string()),
+            base_url,
+        })
+        .unwrap();
+        let path = temp_dir().join(format!("aoc-input-{}", process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(client.cached_input(2022, 1, &path).unwrap(), "1\n2\n");
+        assert_eq!(client.cached_input(2022, 1, &path).unwrap(), "1\n2\n");
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            handler.join().unwrap(),
+            [(
+                "/2022/day/1/input".to_string(),
+                Some("session=secret".to_string())
+            )]
+        );
+    }
+
+    #[test]
+    fn reports_errors() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let handler = thread::spawn(move || {
+            let request = server.recv().unwrap();
+            let response =
+                Response::from_string("Puzzle inputs differ by user.").with_status_code(400);
+            request.respond(response).unwrap();
+        });
+
+        let client = Client::new(&Config {
+            session: Some("expired".to_string()),
+            base_url,
+        })
+        .unwrap();
+        let error = client.download_input(2022, 1).unwrap_err();
+        assert!(error.to_string().contains("400"), "{error}");
+        handler.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answers() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let handler = thread::spawn(move || {
+            let mut request = server.recv().unwrap();
+            let url = request.url().to_string();
+            let mut form = String::new();
+            request.as_reader().read_to_string(&mut form).unwrap();
+            let page = "<article><p>That's not the right answer; your answer is too low. \
+                        Please wait one minute before trying again.</p></article>";
+            request.respond(Response::from_string(page)).unwrap();
+            (url, form)
+        });
+
+        let client = Client::new(&Config {
+            session: Some("secret".to_string()),
+            base_url,
+        })
+        .unwrap();
+        let reply = client.submit_answer(2022, 5, Part::Two, "42").unwrap();
+        assert_eq!(reply.outcome, crate::Outcome::TooLow);
+        assert_eq!(reply.cooldown, Duration::from_secs(60));
+        assert_eq!(
+            handler.join().unwrap(),
+            (
+                "/2022/day/5/answer".to_string(),
+                "level=2&answer=42".to_string()
+            )
+        );
+    }
+}
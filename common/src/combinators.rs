@@ -0,0 +1,404 @@
+use std::str::FromStr;
+
+use crate::ParseError;
+
+/// A position in the text being parsed.
+///
+/// Errors are always created against the whole input, so they report the right line and
+/// column however deeply parsers are nested.
+#[derive(Clone, Copy, Debug)]
+pub struct Cursor<'a> {
+    text: &'a str,
+    position: usize,
+    /// Where the current line or block ends; parsers cannot look past it.
+    end: usize,
+}
+
+/// Parses a value at the cursor and moves the cursor past it.
+///
+/// Implemented by every function or closure of the right shape, so parsers are written as
+/// plain functions and combined with the functions of this module.
+pub trait Parser<'a, T>: Fn(&mut Cursor<'a>) -> Result<T, ParseError> {}
+
+impl<'a, T, F> Parser<'a, T> for F where F: Fn(&mut Cursor<'a>) -> Result<T, ParseError> {}
+
+impl<'a> Cursor<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            position: 0,
+            end: text.len(),
+        }
+    }
+
+    /// The text that is left in the current line or block.
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.position..self.end]
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.position == self.end
+    }
+
+    /// Consumes the next `length` bytes and returns them.
+    pub fn advance(&mut self, length: usize) -> &'a str {
+        let taken = &self.rest()[..length];
+        self.position += length;
+        taken
+    }
+
+    /// Consumes `prefix` if the rest starts with it.
+    pub fn eat(&mut self, prefix: &str) -> bool {
+        let found = self.rest().starts_with(prefix);
+        if found {
+            self.position += prefix.len();
+        }
+        found
+    }
+
+    /// An error pointing at the cursor.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::at_offset(self.text, self.position, message)
+    }
+
+    /// An error pointing at `fragment`, a slice of text this cursor has consumed.
+    pub fn error_at(&self, fragment: &str, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.text, fragment, message)
+    }
+
+    /// An error for unexpected text at the cursor, like `expected a number, found 'x'`.
+    pub fn expected(&self, what: &str) -> ParseError {
+        match self.next_token() {
+            Some(token) => self.error(format!("expected {what}, found '{token}'")),
+            None => self.error(format!("expected {what}")),
+        }
+    }
+
+    /// The word or number at the cursor, or the next character if there is none.
+    fn next_token(&self) -> Option<String> {
+        let rest = self.rest();
+        let word = rest
+            .split(|next: char| !next.is_alphanumeric())
+            .next()
+            .unwrap_or("");
+        match word {
+            "" => rest
+                .chars()
+                .next()
+                .map(|next| next.escape_debug().to_string()),
+            word => Some(word.to_string()),
+        }
+    }
+
+    /// Runs `parser` on the next `length` bytes, which it has to consume completely.
+    fn section<T>(&mut self, length: usize, parser: &impl Parser<'a, T>) -> Result<T, ParseError> {
+        let mut section = Self {
+            end: self.position + length,
+            ..*self
+        };
+        let value = parser(&mut section)?;
+        end(&mut section)?;
+        self.position = section.end;
+        Ok(value)
+    }
+}
+
+/// Parses all of `text`, failing if `parser` leaves anything but a final newline.
+pub fn parse_all<'a, T>(text: &'a str, parser: impl Parser<'a, T>) -> Result<T, ParseError> {
+    let mut cursor = Cursor::new(text);
+    let value = parser(&mut cursor)?;
+    cursor.eat("\n");
+    end(&mut cursor)?;
+    Ok(value)
+}
+
+/// Succeeds only at the end of the current line or block.
+pub fn end(cursor: &mut Cursor) -> Result<(), ParseError> {
+    let mut unexpected = *cursor;
+    take_while(char::is_whitespace)(&mut unexpected)?;
+    if unexpected.is_at_end() {
+        unexpected = *cursor;
+    }
+    match unexpected.next_token() {
+        Some(token) => Err(unexpected.error(format!("unexpected text '{token}'"))),
+        None => Ok(()),
+    }
+}
+
+/// Consumes exactly `expected`.
+///
+/// Errors point at the first word that differs, like `expected 'is at'` after `beacon `.
+pub fn literal<'a>(expected: &'static str) -> impl Parser<'a, ()> {
+    move |cursor: &mut Cursor<'a>| {
+        if cursor.eat(expected) {
+            return Ok(());
+        }
+        let rest = cursor.rest();
+        let matching = rest
+            .char_indices()
+            .zip(expected.chars())
+            .find(|((_, found), wanted)| found != wanted)
+            .map_or(rest.len(), |((index, _), _)| index);
+        let word_start = expected[..matching]
+            .rfind(|next: char| !next.is_alphanumeric())
+            .map_or(0, |index| index + 1);
+        let mut mismatch = *cursor;
+        mismatch.advance(word_start);
+        Err(mismatch.expected(&format!("'{}'", &expected[word_start..])))
+    }
+}
+
+/// Parses `label` followed by `item`, like `Starting items: 79, 98`.
+pub fn labelled<'a, T>(label: &'static str, item: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |cursor: &mut Cursor<'a>| {
+        literal(label)(cursor)?;
+        item(cursor)
+    }
+}
+
+/// Parses whichever of the given literals comes next, into the value paired with it.
+///
+/// Earlier options win, so an option must come before any option that is a prefix of it.
+pub fn one_of<'a, T: Copy>(options: &'static [(&'static str, T)]) -> impl Parser<'a, T> {
+    move |cursor: &mut Cursor<'a>| {
+        for (literal, value) in options {
+            if cursor.eat(literal) {
+                return Ok(*value);
+            }
+        }
+        let expected: Vec<String> = options
+            .iter()
+            .map(|(literal, _)| format!("'{literal}'"))
+            .collect();
+        Err(cursor.expected(&format!("one of {}", expected.join(", "))))
+    }
+}
+
+/// Parses one character that `convert` accepts, like a digit of a height map.
+///
+/// `expected` describes the accepted characters for the error message.
+pub fn character<'a, T>(
+    expected: &'static str,
+    convert: impl Fn(char) -> Option<T>,
+) -> impl Parser<'a, T> {
+    move |cursor: &mut Cursor<'a>| {
+        let Some(next) = cursor.rest().chars().next() else {
+            return Err(cursor.error(format!("expected {expected}")));
+        };
+        let value = convert(next).ok_or_else(|| {
+            cursor.error(format!(
+                "expected {expected}, found '{}'",
+                next.escape_debug()
+            ))
+        })?;
+        cursor.advance(next.len_utf8());
+        Ok(value)
+    }
+}
+
+/// Consumes the longest run of characters matching `predicate`, which may be empty.
+pub fn take_while<'a>(predicate: impl Fn(char) -> bool) -> impl Parser<'a, &'a str> {
+    move |cursor: &mut Cursor<'a>| {
+        let rest = cursor.rest();
+        let length = rest.find(|next| !predicate(next)).unwrap_or(rest.len());
+        Ok(cursor.advance(length))
+    }
+}
+
+/// Consumes a non-empty run of characters up to the next whitespace.
+pub fn word<'a>(cursor: &mut Cursor<'a>) -> Result<&'a str, ParseError> {
+    let word = take_while(|next| !next.is_whitespace())(cursor)?;
+    if word.is_empty() {
+        return Err(cursor.expected("a word"));
+    }
+    Ok(word)
+}
+
+/// Parses a number without sign, like `42`.
+pub fn unsigned<'a, T: FromStr>(cursor: &mut Cursor<'a>) -> Result<T, ParseError> {
+    let digits = take_while(|next| next.is_ascii_digit())(cursor)?;
+    number(cursor, digits)
+}
+
+/// Parses a number with an optional minus sign, like `-42`.
+pub fn signed<'a, T: FromStr>(cursor: &mut Cursor<'a>) -> Result<T, ParseError> {
+    let start = cursor.rest();
+    cursor.eat("-");
+    let digits = take_while(|next| next.is_ascii_digit())(cursor)?;
+    let length = start.len() - cursor.rest().len();
+    if digits.is_empty() {
+        return Err(cursor.expected("a number"));
+    }
+    number(cursor, &start[..length])
+}
+
+fn number<T: FromStr>(cursor: &Cursor, digits: &str) -> Result<T, ParseError> {
+    if digits.is_empty() {
+        return Err(cursor.expected("a number"));
+    }
+    digits
+        .parse()
+        .map_err(|_| cursor.error_at(digits, format!("{digits} is out of range")))
+}
+
+/// Parses one or more `item`s with `separator` between them, like `79, 98`.
+pub fn separated<'a, T>(
+    item: impl Parser<'a, T>,
+    separator: &'static str,
+) -> impl Parser<'a, Vec<T>> {
+    move |cursor: &mut Cursor<'a>| {
+        let mut items = vec![item(cursor)?];
+        while cursor.eat(separator) {
+            items.push(item(cursor)?);
+        }
+        Ok(items)
+    }
+}
+
+/// Parses one or more `item`s up to the end of the current line or block, like the cells of a
+/// row.
+pub fn repeated<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |cursor: &mut Cursor<'a>| {
+        let mut items = vec![item(cursor)?];
+        while !cursor.is_at_end() {
+            items.push(item(cursor)?);
+        }
+        Ok(items)
+    }
+}
+
+/// Parses the rest of the current line with `item`, which has to consume all of it, and
+/// moves past the newline.
+pub fn line<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |cursor: &mut Cursor<'a>| {
+        let rest = cursor.rest();
+        let value = cursor.section(rest.find('\n').unwrap_or(rest.len()), &item)?;
+        cursor.eat("\n");
+        Ok(value)
+    }
+}
+
+/// Parses every line with `item`, which has to consume the whole line.
+pub fn lines<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    let line = line(item);
+    move |cursor: &mut Cursor<'a>| {
+        let mut items = Vec::new();
+        while !cursor.is_at_end() {
+            items.push(line(cursor)?);
+        }
+        Ok(items)
+    }
+}
+
+/// Parses blocks of lines separated by blank lines with `item`, which has to consume the
+/// whole block except for its last newline.
+pub fn blocks<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |cursor: &mut Cursor<'a>| {
+        let mut items = Vec::new();
+        while !cursor.is_at_end() {
+            let rest = cursor.rest();
+            let length = rest
+                .find("\n\n")
+                .unwrap_or(rest.strip_suffix('\n').unwrap_or(rest).len());
+            items.push(cursor.section(length, &item)?);
+            if !cursor.eat("\n\n") {
+                cursor.eat("\n");
+            }
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn point(cursor: &mut Cursor) -> Result<(i32, i32), ParseError> {
+        let x = labelled("x=", signed)(cursor)?;
+        let y = labelled(", y=", signed)(cursor)?;
+        Ok((x, y))
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse_all("42", unsigned::<u8>), Ok(42));
+        assert_eq!(parse_all("-42\n", signed::<i64>), Ok(-42));
+        assert_eq!(parse_all("x=-2, y=15", point), Ok((-2, 15)));
+
+        let error =
+            parse_all("move 3 fro 1", labelled("move 3 from ", unsigned::<u8>)).unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (8, "expected 'from ', found 'fro'")
+        );
+        let error = parse_all("300", unsigned::<u8>).unwrap_err();
+        assert_eq!(error.message, "300 is out of range");
+        let error = parse_all("-x", signed::<i8>).unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (2, "expected a number, found 'x'")
+        );
+        let error = parse_all("12 monkeys", unsigned::<u8>).unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (4, "unexpected text 'monkeys'")
+        );
+        let error = parse_all("12 ", unsigned::<u8>).unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (3, "unexpected text ' '")
+        );
+    }
+
+    #[test]
+    fn structure() {
+        let input = "Monkey 0:\n  Items: 79, 98\n\nMonkey 1:\n  Items: 54\n";
+        let monkey = |cursor: &mut Cursor| {
+            let number: u8 = labelled("Monkey ", unsigned)(cursor)?;
+            literal(":\n")(cursor)?;
+            let items: Vec<u32> = labelled("  Items: ", separated(unsigned, ", "))(cursor)?;
+            Ok((number, items))
+        };
+        assert_eq!(
+            parse_all(input, blocks(monkey)),
+            Ok(vec![(0, vec![79, 98]), (1, vec![54])])
+        );
+
+        let error = parse_all(&input.replace("54", "54, x"), blocks(monkey)).unwrap_err();
+        assert_eq!((error.line, error.column), (5, 14));
+        // the first block cannot swallow the second one
+        let error = parse_all(&input.replace("98", "98, "), blocks(monkey)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 18));
+        assert_eq!(error.message, "expected a number");
+    }
+
+    #[test]
+    fn line_by_line() {
+        let instruction = one_of(&[("noop", 0), ("addx", 1)]);
+        assert_eq!(
+            parse_all("noop\naddx\nnoop\n", lines(&instruction)),
+            Ok(vec![0, 1, 0])
+        );
+        assert_eq!(parse_all("", lines(&instruction)), Ok(vec![]));
+
+        let error = parse_all("noop\nmul 3\n", lines(&instruction)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "expected one of 'noop', 'addx', found 'mul'");
+        let error = parse_all("noop\nnoop 3\n", lines(&instruction)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+    }
+
+    #[test]
+    fn words() {
+        let file = |cursor: &mut Cursor<'static>| {
+            let size: u32 = unsigned(cursor)?;
+            Ok((size, labelled(" ", word)(cursor)?))
+        };
+        assert_eq!(parse_all("14848514 b.txt", file), Ok((14848514, "b.txt")));
+        let error = parse_all("14848514 ", file).unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (10, "expected a word")
+        );
+    }
+}
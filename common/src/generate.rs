@@ -0,0 +1,91 @@
+use std::ops::RangeInclusive;
+
+/// Writes a synthetic puzzle input of roughly `size` elements, e.g. lines or nodes.
+///
+/// What `size` counts is up to each day; its generator documents it along with the size of
+/// the real inputs, so that stress tests know what 10x or 100x means.
+pub type Generator = fn(rng: &mut Rng, size: usize) -> String;
+
+/// A small seeded random number generator (SplitMix64), so that generated inputs can be
+/// reproduced from their seed without pulling in a dependency.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(
+            start <= end,
+            "cannot pick from the empty range {start}..={end}"
+        );
+        let span = end.abs_diff(start).wrapping_add(1);
+        // a span of 0 means the range covers all of i64
+        let offset = if span == 0 {
+            self.next_u64()
+        } else {
+            self.next_u64() % span
+        };
+        start.wrapping_add_unsigned(offset)
+    }
+
+    /// An index into a collection of `len` elements, which must not be empty.
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "cannot pick an index of an empty collection");
+        (self.next_u64() % len as u64) as usize
+    }
+
+    /// Returns true with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= probability
+    }
+
+    pub fn choose<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.index(index + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rng() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..100).map(|_| rng.range(-3..=3)).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(7), numbers(7), "same seed, same numbers");
+        assert_ne!(numbers(7), numbers(8));
+        assert!(numbers(7).iter().all(|number| (-3..=3).contains(number)));
+        assert!((-3..=3).all(|number| numbers(7).contains(&number)));
+
+        let mut rng = Rng::new(1);
+        assert_eq!(rng.range(5..=5), 5);
+        rng.range(i64::MIN..=i64::MAX);
+        let mut items: Vec<_> = (0..10).collect();
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+}
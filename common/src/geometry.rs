@@ -0,0 +1,247 @@
+//! Points, vectors and directions on the puzzle plane.
+//!
+//! x grows to the right and y grows downwards, like rows and columns of the puzzle input.
+//! [`Direction::Up`] is therefore `(0, -1)`.
+
+use std::{
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// A location on the plane.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A displacement between two points.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Sum of the distances along both axes.
+    pub fn manhattan(self, other: Point) -> i64 {
+        (self - other).manhattan_length()
+    }
+
+    /// Largest distance along either axis, i.e. the number of king moves.
+    pub fn chebyshev(self, other: Point) -> i64 {
+        (self - other).chebyshev_length()
+    }
+}
+
+impl Vector {
+    pub const ZERO: Vector = Vector::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_length(self) -> i64 {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn chebyshev_length(self) -> i64 {
+        self.x.abs().max(self.y.abs())
+    }
+
+    /// Reduces both components to -1, 0 or 1.
+    pub fn signum(self) -> Vector {
+        Vector::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Rotates by 90 degrees clockwise as seen on screen.
+    pub fn rotate_right(self) -> Vector {
+        Vector::new(-self.y, self.x)
+    }
+
+    /// Rotates by 90 degrees counterclockwise as seen on screen.
+    pub fn rotate_left(self) -> Vector {
+        Vector::new(self.y, -self.x)
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Vector) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Self::Output {
+        Vector::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Vector {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Self::Output {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Vector::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Display for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}, {}>", self.x, self.y)
+    }
+}
+
+/// One of the four orthogonal directions.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions in clockwise order, starting with [`Direction::Up`].
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The unit vector pointing this way.
+    pub const fn vector(self) -> Vector {
+        match self {
+            Direction::Up => Vector::new(0, -1),
+            Direction::Right => Vector::new(1, 0),
+            Direction::Down => Vector::new(0, 1),
+            Direction::Left => Vector::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.turn_right().turn_right()
+    }
+}
+
+impl From<Direction> for Vector {
+    fn from(direction: Direction) -> Self {
+        direction.vector()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+        assert_eq!(b - a, Vector::new(3, -4));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!((b - a).signum() * 2, Vector::new(2, -2));
+    }
+
+    #[test]
+    fn turning() {
+        assert_eq!(Direction::Up.vector(), Vector::new(0, -1));
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction.vector().rotate_right(),
+                direction.turn_right().vector()
+            );
+            assert_eq!(
+                direction.vector().rotate_left(),
+                direction.turn_left().vector()
+            );
+            assert_eq!(-direction.vector(), direction.reverse().vector());
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use std::{
+    fmt::{self, Display, Write},
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    character, line, lines, parse_all, repeated, Cursor, Direction, ParseError, Point, Vector,
+};
+
+const NEIGHBOURS_8: [Vector; 8] = [
+    Vector::new(0, -1),
+    Vector::new(1, -1),
+    Vector::new(1, 0),
+    Vector::new(1, 1),
+    Vector::new(0, 1),
+    Vector::new(-1, 1),
+    Vector::new(-1, 0),
+    Vector::new(-1, -1),
+];
+
+/// A rectangular grid of cells stored row by row; the first cell is at [`Point::ORIGIN`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row order; `cells.len()` must be a multiple of `width`.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells do not form rows of {width}",
+            cells.len()
+        );
+        Self { width, cells }
+    }
+
+    /// Parses one row per line, turning every character into a cell with `parse_cell`.
+    ///
+    /// `expected` describes valid characters for the error message.
+    pub fn parse<'a>(
+        input: &'a str,
+        expected: &'static str,
+        parse_cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let row = repeated(character(expected, parse_cell));
+        parse_all(input, |cursor: &mut Cursor<'a>| {
+            let mut cells = line(&row)(cursor)?;
+            let width = cells.len();
+            let rows = lines(|cursor: &mut Cursor<'a>| {
+                let start = *cursor;
+                let row = row(cursor)?;
+                if row.len() != width {
+                    return Err(start.error(format!(
+                        "expected {width} cells per row like the first, found {}",
+                        row.len()
+                    )));
+                }
+                Ok(row)
+            })(cursor)?;
+            cells.extend(rows.into_iter().flatten());
+            Ok(Self { width, cells })
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len() / self.width
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (0..self.width as i64).contains(&point.x) && (0..self.height() as i64).contains(&point.y)
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(|offset| &mut self.cells[offset])
+    }
+
+    /// Appends a row at the bottom; it must be exactly `width` cells long.
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = T>) {
+        let start = self.cells.len();
+        self.cells.extend(row);
+        assert_eq!(
+            self.cells.len() - start,
+            self.width,
+            "row has the wrong width"
+        );
+    }
+
+    /// All points in row order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width as i64;
+        (0..self.cells.len() as i64).map(move |offset| Point::new(offset % width, offset / width))
+    }
+
+    /// All cells with their points in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The up to four orthogonally adjacent points inside the grid.
+    pub fn neighbours_4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, Direction::ALL.map(Direction::vector))
+    }
+
+    /// The up to eight orthogonally and diagonally adjacent points inside the grid.
+    pub fn neighbours_8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, NEIGHBOURS_8)
+    }
+
+    fn neighbours<const N: usize>(
+        &self,
+        point: Point,
+        offsets: [Vector; N],
+    ) -> impl Iterator<Item = Point> + '_ {
+        offsets
+            .into_iter()
+            .map(move |offset| point + offset)
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    /// Points from `start` in steps of `step`, excluding `start`, until the edge of the grid.
+    pub fn ray(&self, start: Point, step: Vector) -> impl Iterator<Item = Point> + Clone + '_ {
+        (1..)
+            .map(move |distance| start + step * distance)
+            .take_while(|point| self.contains(*point))
+    }
+
+    /// Renders every cell as one character, one line per row.
+    pub fn display<F>(&self, render: F) -> GridDisplay<'_, T, F>
+    where
+        F: Fn(Point, &T) -> char,
+    {
+        GridDisplay { grid: self, render }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, cell: T) -> Self {
+        Self::from_cells(width, vec![cell; width * height])
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders a [`Grid`] with a custom character per cell, see [`Grid::display`].
+pub struct GridDisplay<'a, T, F> {
+    grid: &'a Grid<T>,
+    render: F,
+}
+
+impl<T, F> Display for GridDisplay<'_, T, F>
+where
+    F: Fn(Point, &T) -> char,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (point, cell) in self.grid.iter() {
+            f.write_char((self.render)(point, cell))?;
+            if point.x as usize == self.grid.width - 1 {
+                f.write_char('\n')?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        Grid::parse("123\n456\n", "a digit", |symbol| symbol.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parse_and_index() {
+        let grid = digits();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 6);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+
+        let error = Grid::parse("12\n4x\n", "a digit", |symbol| symbol.to_digit(10)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        let error = Grid::parse("12\n456\n", "a digit", |symbol| symbol.to_digit(10)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = digits();
+        assert_eq!(
+            grid.neighbours_4(Point::ORIGIN).collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours_8(Point::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn rows_columns_and_rays() {
+        let mut grid = digits();
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.column(1).collect::<Vec<_>>(), [&2, &5]);
+        assert_eq!(
+            grid.ray(Point::ORIGIN, Direction::Right.vector())
+                .collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(2, 0)]
+        );
+
+        grid.push_row([7, 8, 9]);
+        assert_eq!(grid.columns().last().unwrap().next_back(), Some(&9));
+        assert_eq!(grid.to_string(), "123\n456\n789\n");
+        assert_eq!(
+            grid.display(|_, cell| if cell % 2 == 0 { '#' } else { '.' })
+                .to_string(),
+            ".#.\n#.#\n.#.\n"
+        );
+    }
+}
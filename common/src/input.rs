@@ -0,0 +1,109 @@
+use std::{
+    fs::read_to_string,
+    io::{read_to_string as read_all, stdin},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use eyre::{bail, Context, Result};
+
+use crate::Day;
+
+/// Where a day reads its puzzle input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The file `input` in the day's directory.
+    #[default]
+    Default,
+    Path(PathBuf),
+    Stdin,
+    /// The example from the puzzle description, bundled with the day's crate.
+    Example,
+}
+
+impl InputSource {
+    pub fn read(&self, day: &Day) -> Result<String> {
+        match self {
+            InputSource::Default => read_file(&day.input_path()),
+            InputSource::Path(path) => read_file(path),
+            InputSource::Stdin => read_all(stdin()).wrap_err("failed to read input from stdin"),
+            InputSource::Example => match day.example {
+                "" => bail!("day {} has no bundled example", day.day),
+                example => Ok(example.to_string()),
+            },
+        }
+    }
+
+    /// The file name this input has in the day's directory, which keys its known answers.
+    ///
+    /// Inputs from stdin or from other directories have no name.
+    pub fn name(&self, day: &Day) -> Option<String> {
+        match self {
+            InputSource::Default => Some("input".to_string()),
+            InputSource::Example => Some("example".to_string()),
+            InputSource::Path(path) => {
+                let directory = Path::new(day.directory).canonicalize().ok()?;
+                let parent = path.canonicalize().ok()?.parent()?.to_path_buf();
+                (parent == directory).then(|| path.file_name()?.to_str().map(String::from))?
+            }
+            InputSource::Stdin => None,
+        }
+    }
+}
+
+/// Parses a path given on the command line, where `-` stands for stdin.
+impl FromStr for InputSource {
+    type Err = std::convert::Infallible;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Ok(match text {
+            "-" => InputSource::Stdin,
+            path => InputSource::Path(PathBuf::from(path)),
+        })
+    }
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    if !path.is_file() {
+        bail!("no puzzle input found at {}", path.display());
+    }
+    read_to_string(path).wrap_err_with(|| format!("failed to read {}", path.display()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Answer, ParseError, Solution};
+
+    struct Echo(String);
+
+    impl Solution for Echo {
+        fn parse(input: &str) -> Result<Self, ParseError> {
+            Ok(Echo(input.to_string()))
+        }
+
+        fn part_1(&self) -> Result<Answer> {
+            Ok(self.0.as_str().into())
+        }
+
+        fn part_2(&self) -> Result<Answer> {
+            Ok(self.0.as_str().into())
+        }
+    }
+
+    const DAY: Day = Day::new::<Echo>(1, "Echo", "/nonexistent").with_example("example\n");
+
+    #[test]
+    fn sources() {
+        assert_eq!(InputSource::Example.read(&DAY).unwrap(), "example\n");
+        assert_eq!("-".parse(), Ok(InputSource::Stdin));
+        assert_eq!(InputSource::Default.name(&DAY).as_deref(), Some("input"));
+        assert_eq!(InputSource::Stdin.name(&DAY), None);
+
+        let error = InputSource::Default.read(&DAY).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "no puzzle input found at /nonexistent/input"
+        );
+    }
+}
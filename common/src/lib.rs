@@ -0,0 +1,160 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use eyre::Result;
+use sha2::{Digest, Sha256};
+use tracing::info_span;
+
+mod allocations;
+mod answer;
+mod answers;
+mod bench;
+mod client;
+mod combinators;
+mod generate;
+mod geometry;
+mod grid;
+mod input;
+mod parameters;
+mod parse;
+mod property;
+mod recording;
+mod solution;
+mod submit;
+mod verify;
+
+pub use allocations::{measure_allocations, Allocations, CountingAllocator};
+pub use answer::Answer;
+pub use answers::{KnownAnswer, KnownAnswers};
+pub use bench::{bench, BenchHistory, Compact, HistoryEntry, Measurement, Timing};
+pub use client::{Client, Config};
+pub use combinators::{
+    blocks, character, end, labelled, line, lines, literal, one_of, parse_all, repeated, separated,
+    signed, take_while, unsigned, word, Cursor, Parser,
+};
+pub use generate::{Generator, Rng};
+pub use geometry::{Direction, Point, Vector};
+pub use grid::{Grid, GridDisplay};
+pub use input::InputSource;
+pub use parameters::{parse_parameter, Parameter, Parameters};
+pub use parse::ParseError;
+pub use property::check_property;
+pub use recording::{save_recording, write_cast, write_gif, Frame, Recorder};
+pub use solution::{Day, Part, Solution, Variant, Year};
+pub use submit::{submit, Attempt, Ledger, Outcome, Reply};
+pub use verify::{
+    known_examples, unverified_examples, verify, verify_examples, Verdict, Verification,
+};
+
+/// A part solved by [`solve`], with where its answer came from and how long it took.
+#[derive(Clone, Debug)]
+pub struct Solved {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Answer,
+    /// Name of the input in the day's directory, see [`InputSource::name`].
+    pub input: Option<String>,
+    /// SHA-256 of the input text as lowercase hex.
+    pub input_hash: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// What solving the part allocated, with the `count-allocations` feature.
+    pub allocations: Option<Allocations>,
+}
+
+/// Solves the given parts of a day and prints the answers.
+pub fn run(day: &Day, source: &InputSource, parts: &[Part]) -> Result<()> {
+    solve(day, source, parts, &Parameters::default(), print_answer).map(|_| ())
+}
+
+/// Solves the given parts and hands every answer to `report` as soon as it is solved.
+///
+/// Answers that contradict known ones are flagged on stderr, unless `parameters` changes the
+/// puzzle, which makes the known answers meaningless.
+pub fn solve(
+    day: &Day,
+    source: &InputSource,
+    parts: &[Part],
+    parameters: &Parameters,
+    mut report: impl FnMut(&Solved),
+) -> Result<Vec<Solved>> {
+    let _day = info_span!("day", day = day.day).entered();
+    let input = source.read(day)?;
+    let input_hash = format!("{:x}", Sha256::digest(&input));
+    let start = Instant::now();
+    let name = source.name(day);
+    let solution =
+        info_span!("parse").in_scope(|| day.parse_with(&input, name.as_deref(), parameters))?;
+    let parse_time = start.elapsed();
+
+    let known = if parameters.is_empty() {
+        KnownAnswers::load(day)?
+    } else {
+        KnownAnswers::default()
+    };
+
+    let mut solved = Vec::new();
+    for &part in parts {
+        let start = Instant::now();
+        let (answer, allocations) = measure_allocations(|| {
+            info_span!("solve", part = part.number()).in_scope(|| solution.solve(part))
+        });
+        let answer = answer?;
+        let solve_time = start.elapsed();
+
+        let expected = name.as_deref().and_then(|name| known.get(name, part));
+        if let Some(expected) = expected.filter(|expected| *expected != answer.to_string()) {
+            eprintln!("warning: {part} differs from the known answer {expected}");
+        }
+
+        let part = Solved {
+            day: day.day,
+            part,
+            answer,
+            input: name.clone(),
+            input_hash: input_hash.clone(),
+            parse_time,
+            solve_time,
+            allocations,
+        };
+        report(&part);
+        solved.push(part);
+    }
+
+    Ok(solved)
+}
+
+/// Prints an answer as `Part 1: 24000`, with multi-line answers starting on the next line.
+///
+/// Counted allocations follow on their own line.
+pub fn print_answer(solved: &Solved) {
+    let Solved {
+        part,
+        answer,
+        allocations,
+        ..
+    } = solved;
+    if answer.is_multiline() {
+        println!("{part}:\n{answer}");
+    } else {
+        println!("{part}: {answer}");
+    }
+    if let Some(allocations) = allocations {
+        println!("  {allocations}");
+    }
+}
+
+/// Records answers as accepted for the input with the given name.
+pub fn save_answers(day: &Day, input: &str, solved: &[Solved]) -> Result<()> {
+    let mut known = KnownAnswers::load(day)?;
+    for Solved { part, answer, .. } in solved {
+        known.insert(input, *part, answer.to_string());
+    }
+    known.save(day)
+}
+
+/// Seconds since the Unix epoch, used to timestamp local records.
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
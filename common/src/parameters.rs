@@ -0,0 +1,144 @@
+use std::{fmt::Display, fs::read_to_string, path::Path, str::FromStr};
+
+use eyre::{eyre, Context, Result};
+
+use crate::{lines, literal, parse_all, take_while, Cursor, ParseError};
+
+/// A puzzle constant that can be changed to solve made-up variants of a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Parameter {
+    pub name: &'static str,
+    /// The value of the puzzle, used unless another one is given.
+    pub default: &'static str,
+    /// The value the example of the puzzle description uses, if it differs from `default`.
+    pub example: Option<&'static str>,
+    pub description: &'static str,
+}
+
+impl Parameter {
+    pub const fn new(name: &'static str, default: &'static str, description: &'static str) -> Self {
+        Self {
+            name,
+            default,
+            example: None,
+            description,
+        }
+    }
+
+    pub const fn with_example(self, example: &'static str) -> Self {
+        Self {
+            example: Some(example),
+            ..self
+        }
+    }
+}
+
+/// Values given for the parameters of a day, by name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Parameters {
+    values: Vec<(String, String)>,
+}
+
+impl Parameters {
+    /// Reads `name = value` lines; blank lines and lines starting with `#` are skipped.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text =
+            read_to_string(path).wrap_err_with(|| format!("failed to read {}", path.display()))?;
+        let mut parameters = Self::default();
+        for (name, value) in assignments(&text)
+            .wrap_err_with(|| format!("invalid parameter in {}", path.display()))?
+        {
+            parameters.set(name, value);
+        }
+        Ok(parameters)
+    }
+
+    /// Sets a parameter from an assignment like `row=10`, replacing an earlier value.
+    pub fn assign(&mut self, assignment: &str) -> Result<(), ParseError> {
+        let (name, value) = parse_all(assignment, self::assignment)?;
+        self.set(name, value);
+        Ok(())
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) {
+        match self.values.iter_mut().find(|(known, _)| known == name) {
+            Some((_, known)) => *known = value.to_string(),
+            None => self.values.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+/// Parses `name = value` lines; blank lines and lines starting with `#` are skipped.
+pub(crate) fn assignments<'a>(text: &'a str) -> Result<Vec<(&'a str, &'a str)>, ParseError> {
+    let line = |cursor: &mut Cursor<'a>| {
+        let rest = cursor.rest().trim_start();
+        if rest.is_empty() || rest.starts_with('#') {
+            cursor.advance(cursor.rest().len());
+            return Ok(None);
+        }
+        assignment(cursor).map(Some)
+    };
+    Ok(parse_all(text, lines(line))?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+/// Parses an assignment like `row = 10` up to the end of the line.
+fn assignment<'a>(cursor: &mut Cursor<'a>) -> Result<(&'a str, &'a str), ParseError> {
+    take_while(char::is_whitespace)(cursor)?;
+    let name = take_while(|next| next != '=' && !next.is_whitespace())(cursor)?;
+    if name.is_empty() {
+        return Err(cursor.expected("'name = value'"));
+    }
+    take_while(char::is_whitespace)(cursor)?;
+    literal("=")(cursor)?;
+    take_while(char::is_whitespace)(cursor)?;
+    Ok((name, cursor.advance(cursor.rest().len()).trim_end()))
+}
+
+/// Parses the value of a parameter for [`Solution::set_parameter`](crate::Solution::set_parameter).
+pub fn parse_parameter<T>(name: &str, value: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|error| eyre!("invalid value '{value}' for parameter '{name}': {error}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn assign() {
+        let mut parameters = Parameters::default();
+        parameters.assign("row=10").unwrap();
+        parameters.assign("max = 20").unwrap();
+        parameters.assign("row=11").unwrap();
+        assert_eq!(
+            parameters.iter().collect::<Vec<_>>(),
+            [("row", "11"), ("max", "20")]
+        );
+        let error = parameters.assign("row").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+        let error = assignments("# limits\nmax = 20\n\n=3\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+
+        assert_eq!(parse_parameter::<i64>("row", "-3").unwrap(), -3);
+        let error = parse_parameter::<u32>("row", "x").unwrap_err();
+        assert!(error.to_string().contains("parameter 'row'"));
+    }
+}
@@ -0,0 +1,90 @@
+use std::{error::Error, fmt::Display};
+
+/// An error in the puzzle input, pointing at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the error, starting at 1.
+    pub line: usize,
+    /// Column of the error in characters, starting at 1.
+    pub column: usize,
+    /// The full line the error is on.
+    pub snippet: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Creates an error pointing at `fragment`, which has to be a slice of `text`.
+    pub fn new(text: &str, fragment: &str, message: impl Into<String>) -> Self {
+        Self::at_offset(text, offset_in(text, fragment), message)
+    }
+
+    /// Creates an error pointing just past the end of `text`, for input that ends too early.
+    pub fn end_of(text: &str, message: impl Into<String>) -> Self {
+        Self::at_offset(text, text.len(), message)
+    }
+
+    pub(crate) fn at_offset(text: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        let line_end = text[offset..]
+            .find('\n')
+            .map_or(text.len(), |index| offset + index);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            snippet: text[line_start..line_end].to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "  {}", self.snippet)?;
+        write!(f, "  {:>width$}", "^", width = self.column)
+    }
+}
+
+impl Error for ParseError {}
+
+fn offset_in(text: &str, fragment: &str) -> usize {
+    let offset = (fragment.as_ptr() as usize).wrapping_sub(text.as_ptr() as usize);
+    assert!(
+        offset + fragment.len() <= text.len(),
+        "fragment is not a slice of text"
+    );
+    offset
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn position() {
+        let input = "1-2\n3-x\n";
+        let line = input.lines().nth(1).unwrap();
+        let error = ParseError::new(input, &line[2..], "expected a number");
+
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 3);
+        assert_eq!(error.snippet, "3-x");
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected a number\n  3-x\n    ^"
+        );
+    }
+
+    #[test]
+    fn end_of_input() {
+        let error = ParseError::end_of("move 1 from", "expected 'to'");
+
+        assert_eq!((error.line, error.column), (1, 12));
+    }
+}
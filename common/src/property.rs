@@ -0,0 +1,56 @@
+use std::{
+    env,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use crate::Rng;
+
+/// Runs `property` on `cases` random inputs of growing size, from 1 up to `max_size`.
+///
+/// The property gets a generator seeded with the case number in the high and the size in the low
+/// 32 bits, and asserts like a test. The first failure panics with its seed and size, so that it
+/// can be replayed with `Rng::new(seed)`. Small cases come first, which keeps failures easy to read.
+/// `AOC_PROPERTY_CASES` overrides the number of cases, e.g. for a longer run before a commit.
+pub fn check_property(cases: usize, max_size: usize, mut property: impl FnMut(&mut Rng, usize)) {
+    let cases = env::var("AOC_PROPERTY_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(cases)
+        .max(1);
+
+    for case in 0..cases {
+        let size = 1 + case * max_size.saturating_sub(1) / cases;
+        let seed = (case as u64) << 32 | size as u64;
+        let result = catch_unwind(AssertUnwindSafe(|| property(&mut Rng::new(seed), size)));
+        if let Err(panic) = result {
+            let message = match (panic.downcast_ref::<&str>(), panic.downcast_ref::<String>()) {
+                (Some(message), _) => message,
+                (_, Some(message)) => message.as_str(),
+                _ => "unknown cause",
+            };
+            panic!("property failed for seed {seed} and size {size}: {message}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn passing() {
+        let mut sizes = Vec::new();
+        check_property(10, 100, |rng, size| {
+            sizes.push(size);
+            assert!(rng.range(0..=size as i64) <= size as i64);
+        });
+        assert_eq!(sizes.first(), Some(&1));
+        assert!(sizes.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "property failed for seed 214748364850 and size 50: too big")]
+    fn failing() {
+        check_property(100, 100, |_, size| assert!(size < 50, "too big"));
+    }
+}
@@ -0,0 +1,311 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    time::Duration,
+};
+
+use eyre::{bail, eyre, Context, Result};
+use gif::{Encoder, Repeat};
+
+/// Collects text frames from a simulation, see [`Solution::visualize`](crate::Solution::visualize).
+///
+/// A disabled recorder ignores all frames, so simulations can push frames unconditionally.
+/// Frames beyond the limit are dropped as well, because some simulations run for a long time.
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    frames: Option<Vec<Frame>>,
+    delay: Duration,
+    max_frames: usize,
+    /// Only keep the frame passed to [`Recorder::finish`].
+    only_final: bool,
+}
+
+/// One rendered state of a simulation, one line per row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub text: String,
+    /// How long the frame stays on screen.
+    pub delay: Duration,
+}
+
+impl Recorder {
+    pub fn disabled() -> Self {
+        Self {
+            frames: None,
+            delay: Duration::ZERO,
+            max_frames: 0,
+            only_final: false,
+        }
+    }
+
+    /// Records up to `max_frames` frames, each shown for `delay` unless pushed with its own.
+    pub fn new(delay: Duration, max_frames: usize) -> Self {
+        Self {
+            frames: Some(Vec::new()),
+            delay,
+            max_frames,
+            only_final: false,
+        }
+    }
+
+    /// Keeps only the final state of a simulation, without rendering the frames before it.
+    pub fn last_frame() -> Self {
+        Self {
+            frames: Some(Vec::new()),
+            delay: Duration::ZERO,
+            max_frames: 0,
+            only_final: true,
+        }
+    }
+
+    /// Whether another frame would be kept; use it to skip work that only serves frames.
+    pub fn is_recording(&self) -> bool {
+        self.frames
+            .as_ref()
+            .is_some_and(|frames| frames.len() < self.max_frames)
+    }
+
+    pub fn push(&mut self, frame: impl Display) {
+        self.push_with_delay(frame, self.delay);
+    }
+
+    /// Pushes a frame that stays on screen longer or shorter than the others, e.g. the last one.
+    pub fn push_with_delay(&mut self, frame: impl Display, delay: Duration) {
+        if !self.is_recording() {
+            return;
+        }
+        if let Some(frames) = &mut self.frames {
+            frames.push(Frame {
+                text: frame.to_string(),
+                delay,
+            });
+        }
+    }
+
+    /// Pushes the final state of a simulation, which is kept even past the frame limit.
+    ///
+    /// It is skipped if it looks the same as the frame pushed last.
+    pub fn finish(&mut self, frame: impl Display) {
+        let delay = self.delay;
+        if let Some(frames) = &mut self.frames {
+            let text = frame.to_string();
+            if self.only_final {
+                frames.clear();
+            } else if frames.last().is_some_and(|last| last.text == text) {
+                return;
+            }
+            frames.push(Frame { text, delay });
+        }
+    }
+
+    pub fn into_frames(self) -> Vec<Frame> {
+        self.frames.unwrap_or_default()
+    }
+}
+
+/// Writes frames to `path` as an animated GIF or an asciinema cast, depending on its extension.
+pub fn save_recording(frames: &[Frame], path: &Path, title: &str) -> Result<()> {
+    if frames.is_empty() {
+        bail!("nothing was recorded");
+    }
+    let file =
+        File::create(path).wrap_err_with(|| format!("failed to create {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("gif") => write_gif(frames, &mut writer, 4)?,
+        Some("cast") => write_cast(frames, &mut writer, title)?,
+        _ => bail!(
+            "unknown recording format of {}, expected .gif or .cast",
+            path.display()
+        ),
+    }
+    writer
+        .flush()
+        .wrap_err_with(|| format!("failed to write {}", path.display()))
+}
+
+fn size(frames: &[Frame]) -> (usize, usize) {
+    let width = frames
+        .iter()
+        .flat_map(|frame| frame.text.lines())
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let height = frames
+        .iter()
+        .map(|frame| frame.text.lines().count())
+        .max()
+        .unwrap_or(0);
+    (width, height)
+}
+
+/// Writes an [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/) file.
+pub fn write_cast(frames: &[Frame], writer: &mut impl Write, title: &str) -> Result<()> {
+    let (width, height) = size(frames);
+    let header = serde_json::json!({
+        "version": 2,
+        "width": width,
+        "height": height,
+        "title": title,
+    });
+    writeln!(writer, "{header}")?;
+
+    let mut time = Duration::ZERO;
+    for frame in frames {
+        // clear the screen and draw the frame from the top left corner
+        let output = format!("\x1b[H\x1b[2J{}", frame.text.replace('\n', "\r\n"));
+        let event = serde_json::json!([time.as_secs_f64(), "o", output]);
+        writeln!(writer, "{event}")?;
+        time += frame.delay;
+    }
+    Ok(())
+}
+
+/// Writes an animated GIF that draws every character as a square of `cell` pixels.
+pub fn write_gif(frames: &[Frame], writer: &mut impl Write, cell: usize) -> Result<()> {
+    let (columns, rows) = size(frames);
+    let width = u16::try_from(columns * cell).map_err(|_| eyre!("frames are too wide"))?;
+    let height = u16::try_from(rows * cell).map_err(|_| eyre!("frames are too high"))?;
+
+    // the background is always the first palette entry, so padding uses index 0
+    let mut symbols = vec![' '];
+    let mut indices: HashMap<char, u8> = HashMap::from([(' ', 0), ('.', 0)]);
+    for symbol in frames.iter().flat_map(|frame| frame.text.chars()) {
+        if symbol != '\n' && !indices.contains_key(&symbol) {
+            let index = u8::try_from(symbols.len()).map_err(|_| eyre!("too many colors"))?;
+            indices.insert(symbol, index);
+            symbols.push(symbol);
+        }
+    }
+    let palette: Vec<u8> = symbols.iter().flat_map(|symbol| color(*symbol)).collect();
+
+    let mut encoder = Encoder::new(writer, width, height, &palette)?;
+    encoder.set_repeat(Repeat::Infinite)?;
+    for frame in frames {
+        let mut pixels = vec![0; usize::from(width) * usize::from(height)];
+        for (row, line) in frame.text.lines().enumerate() {
+            for (column, symbol) in line.chars().enumerate() {
+                let index = indices[&symbol];
+                for y in row * cell..(row + 1) * cell {
+                    let start = y * usize::from(width) + column * cell;
+                    pixels[start..start + cell].fill(index);
+                }
+            }
+        }
+
+        let mut gif_frame = gif::Frame::from_indexed_pixels(width, height, pixels, None);
+        gif_frame.delay = u16::try_from(frame.delay.as_millis() / 10).unwrap_or(u16::MAX);
+        encoder.write_frame(&gif_frame)?;
+    }
+    Ok(())
+}
+
+/// The color a character is drawn in; lowercase letters become a gradient like a height map.
+fn color(symbol: char) -> [u8; 3] {
+    match symbol {
+        ' ' | '.' => [16, 16, 32],
+        '#' => [200, 200, 200],
+        '@' => [255, 140, 0],
+        'o' => [230, 200, 90],
+        '|' | '-' | '+' => [110, 110, 110],
+        '^' | 'v' | 'V' | '<' | '>' => [80, 210, 120],
+        'S' | 'E' => [230, 60, 60],
+        'a'..='z' => {
+            let level = (symbol as u8 - b'a') * 7;
+            [20, 40 + level, 80 + level]
+        }
+        _ => {
+            // spread other characters over distinct but stable colors
+            let hash = (symbol as u32).wrapping_mul(2_654_435_761);
+            [
+                hash as u8 | 64,
+                (hash >> 8) as u8 | 64,
+                (hash >> 16) as u8 | 64,
+            ]
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn recorder() {
+        let mut recorder = Recorder::disabled();
+        recorder.push("#.");
+        assert!(!recorder.is_recording());
+        assert!(recorder.into_frames().is_empty());
+
+        let mut recorder = Recorder::new(Duration::from_millis(50), 2);
+        recorder.push("#.\n.#");
+        recorder.push_with_delay(".#\n#.", Duration::from_secs(1));
+        recorder.push("dropped");
+        let frames = recorder.into_frames();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[1].delay, Duration::from_secs(1));
+
+        let mut recorder = Recorder::new(Duration::from_millis(50), 1);
+        recorder.push("first");
+        recorder.push("dropped");
+        recorder.finish("last");
+        recorder.finish("last");
+        let texts: Vec<_> = recorder
+            .into_frames()
+            .into_iter()
+            .map(|frame| frame.text)
+            .collect();
+        assert_eq!(texts, ["first", "last"]);
+
+        let mut recorder = Recorder::last_frame();
+        assert!(!recorder.is_recording());
+        recorder.push("first");
+        recorder.finish("last");
+        assert_eq!(
+            recorder.into_frames(),
+            [Frame {
+                text: "last".to_string(),
+                delay: Duration::ZERO
+            }]
+        );
+    }
+
+    #[test]
+    fn cast() {
+        let frames = [
+            Frame {
+                text: "#.\n.#\n".to_string(),
+                delay: Duration::from_millis(500),
+            },
+            Frame {
+                text: "@".to_string(),
+                delay: Duration::from_millis(500),
+            },
+        ];
+        let mut cast = Vec::new();
+        write_cast(&frames, &mut cast, "test").unwrap();
+        let cast = String::from_utf8(cast).unwrap();
+        let lines: Vec<_> = cast.lines().collect();
+        assert_eq!(
+            lines[0],
+            r#"{"height":2,"title":"test","version":2,"width":2}"#
+        );
+        assert_eq!(lines[1], r#"[0.0,"o","\u001b[H\u001b[2J#.\r\n.#\r\n"]"#);
+        assert_eq!(lines[2], r#"[0.5,"o","\u001b[H\u001b[2J@"]"#);
+    }
+
+    #[test]
+    fn gif() {
+        let frames = [Frame {
+            text: "#.\n.@\n".to_string(),
+            delay: Duration::from_millis(100),
+        }];
+        let mut gif = Vec::new();
+        write_gif(&frames, &mut gif, 3).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+        assert_eq!(&gif[6..10], [6, 0, 6, 0], "width and height in pixels");
+    }
+}
@@ -0,0 +1,247 @@
+use std::{fmt::Display, path::PathBuf, str::FromStr};
+
+use eyre::{bail, eyre, Result};
+
+use crate::{
+    verify::is_example, Answer, Generator, Parameter, Parameters, ParseError, Recorder, Rng,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Part {}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = eyre::Report;
+
+    fn from_str(text: &str) -> Result<Self> {
+        match text {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => bail!("invalid part '{text}', expected 1 or 2"),
+        }
+    }
+}
+
+/// An alternative algorithm for one part, kept to compare it against the default one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Variant {
+    pub name: &'static str,
+    pub part: Part,
+}
+
+/// A day's parsed puzzle input together with the solvers for both parts.
+pub trait Solution {
+    fn parse(input: &str) -> Result<Self, ParseError>
+    where
+        Self: Sized;
+
+    fn part_1(&self) -> Result<Answer>;
+
+    fn part_2(&self) -> Result<Answer>;
+
+    fn solve(&self, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => self.part_1(),
+            Part::Two => self.part_2(),
+        }
+    }
+
+    /// Alternative algorithms that can be benchmarked against [`Solution::solve`].
+    fn variants(&self) -> &'static [Variant] {
+        &[]
+    }
+
+    /// Solves with the alternative algorithm of the given name, see [`Solution::variants`].
+    fn solve_variant(&self, name: &str) -> Result<Answer> {
+        bail!("unknown variant '{name}'")
+    }
+
+    /// Changes a puzzle constant declared with [`Day::with_parameters`].
+    ///
+    /// It is called after parsing, once for every value that differs from the puzzle's.
+    fn set_parameter(&mut self, name: &str, value: &str) -> Result<()> {
+        let _ = value;
+        bail!("unknown parameter '{name}'")
+    }
+
+    /// Solves a part like [`Solution::solve`] while pushing the states of its simulation
+    /// into `recorder`.
+    fn visualize(&self, part: Part, recorder: &mut Recorder) -> Result<Answer> {
+        let _ = recorder;
+        bail!("{part} has no visualization")
+    }
+}
+
+/// Registration of a [`Solution`] so that it can be looked up and run by day number.
+pub struct Day {
+    pub day: u8,
+    pub title: &'static str,
+    /// Directory of the day's crate, where its puzzle input is stored.
+    pub directory: &'static str,
+    /// The example from the puzzle description, empty if the day has none.
+    pub example: &'static str,
+    /// Puzzle constants that can be changed, see [`Solution::set_parameter`].
+    pub parameters: &'static [Parameter],
+    generator: Option<Generator>,
+    parse: fn(&str) -> Result<Box<dyn Solution>, ParseError>,
+}
+
+impl Day {
+    pub const fn new<S>(day: u8, title: &'static str, directory: &'static str) -> Self
+    where
+        S: Solution + 'static,
+    {
+        Self {
+            day,
+            title,
+            directory,
+            example: "",
+            parameters: &[],
+            generator: None,
+            parse: parse_boxed::<S>,
+        }
+    }
+
+    pub const fn with_example(self, example: &'static str) -> Self {
+        Self { example, ..self }
+    }
+
+    pub const fn with_parameters(self, parameters: &'static [Parameter]) -> Self {
+        Self { parameters, ..self }
+    }
+
+    pub const fn with_generator(self, generator: Generator) -> Self {
+        Self {
+            generator: Some(generator),
+            ..self
+        }
+    }
+
+    /// Writes a synthetic input of about `size` elements, the same one for the same seed.
+    pub fn generate(&self, seed: u64, size: usize) -> Result<String> {
+        let generator = self
+            .generator
+            .ok_or_else(|| eyre!("day {} has no input generator", self.day))?;
+        Ok(generator(&mut Rng::new(seed), size))
+    }
+
+    pub fn input_path(&self) -> PathBuf {
+        PathBuf::from(self.directory).join("input")
+    }
+
+    /// Where the accepted answers of this day are recorded.
+    pub fn answers_path(&self) -> PathBuf {
+        PathBuf::from(self.directory).join("answers")
+    }
+
+    /// Where the answers submitted for this day are recorded.
+    pub fn submissions_path(&self) -> PathBuf {
+        PathBuf::from(self.directory).join("submissions")
+    }
+
+    /// Where the results of earlier benchmarks of this day are kept.
+    pub fn bench_history_path(&self) -> PathBuf {
+        PathBuf::from(self.directory).join("bench-history")
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Solution>, ParseError> {
+        (self.parse)(input)
+    }
+
+    /// Parses an input and sets the puzzle constants that differ from the puzzle's.
+    ///
+    /// Examples, going by the name of the input, use the values of the puzzle description
+    /// unless `parameters` overrides them.
+    pub fn parse_with(
+        &self,
+        input: &str,
+        name: Option<&str>,
+        parameters: &Parameters,
+    ) -> Result<Box<dyn Solution>> {
+        let mut solution = self.parse(input)?;
+        if name.is_some_and(is_example) {
+            for parameter in self.parameters {
+                if let Some(example) = parameter.example {
+                    solution.set_parameter(parameter.name, example)?;
+                }
+            }
+        }
+        for (name, value) in parameters.iter() {
+            if !self
+                .parameters
+                .iter()
+                .any(|parameter| parameter.name == name)
+            {
+                let known: Vec<_> = self
+                    .parameters
+                    .iter()
+                    .map(|parameter| parameter.name)
+                    .collect();
+                if known.is_empty() {
+                    bail!("day {} has no parameters", self.day);
+                }
+                bail!(
+                    "day {} has no parameter '{name}', only {}",
+                    self.day,
+                    known.join(", ")
+                );
+            }
+            solution.set_parameter(name, value)?;
+        }
+        Ok(solution)
+    }
+}
+
+/// The registered days of one Advent of Code event, in order.
+pub struct Year {
+    pub year: u16,
+    pub days: &'static [Day],
+}
+
+impl Year {
+    pub const fn new(year: u16, days: &'static [Day]) -> Self {
+        Self { year, days }
+    }
+
+    pub fn find(&self, day: u8) -> Option<&'static Day> {
+        self.days.iter().find(|registered| registered.day == day)
+    }
+}
+
+fn parse_boxed<S>(input: &str) -> Result<Box<dyn Solution>, ParseError>
+where
+    S: Solution + 'static,
+{
+    Ok(Box::new(S::parse(input)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn part_from_str() {
+        assert_eq!("1".parse::<Part>().unwrap(), Part::One);
+        assert_eq!("2".parse::<Part>().unwrap(), Part::Two);
+        assert!("3".parse::<Part>().is_err());
+    }
+}
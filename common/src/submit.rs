@@ -0,0 +1,460 @@
+use std::{
+    fmt::{self, Display},
+    fs::{read_to_string, OpenOptions},
+    io::Write,
+    str::FromStr,
+    time::Duration,
+};
+
+use eyre::{bail, eyre, Context, Result};
+
+use crate::{
+    labelled, lines, parse_all, take_while, unix_time, unsigned, Client, Cursor, Day, InputSource,
+    KnownAnswers, ParseError, Part,
+};
+
+/// How the server judged a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// The answer was not checked because the previous attempt was too recent.
+    RateLimited,
+    /// The part was already solved on the server, e.g. from a browser.
+    AlreadySolved,
+}
+
+impl Outcome {
+    const NAMES: [(Outcome, &'static str); 6] = [
+        (Outcome::Correct, "correct"),
+        (Outcome::TooHigh, "too high"),
+        (Outcome::TooLow, "too low"),
+        (Outcome::Wrong, "wrong"),
+        (Outcome::RateLimited, "rate limited"),
+        (Outcome::AlreadySolved, "already solved"),
+    ];
+
+    /// Whether the server rejected this value, so that submitting it again is pointless.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, name) = Outcome::NAMES
+            .iter()
+            .find(|(outcome, _)| outcome == self)
+            .expect("every outcome has a name");
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = eyre::Report;
+
+    fn from_str(text: &str) -> Result<Self> {
+        Outcome::NAMES
+            .iter()
+            .find(|(_, name)| *name == text)
+            .map(|(outcome, _)| *outcome)
+            .ok_or_else(|| eyre!("unknown outcome '{text}'"))
+    }
+}
+
+/// The server's verdict on a submission and how long it wants us to wait before the next one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Reply {
+    pub outcome: Outcome,
+    pub cooldown: Duration,
+}
+
+impl Reply {
+    /// Recognises the verdict in the HTML page returned for a submission, ignoring case.
+    pub fn parse(page: &str) -> Result<Self> {
+        let page = page.to_lowercase();
+        let wait = wait_time(&page)?;
+        let (outcome, cooldown) = if page.contains("that's the right answer") {
+            (Outcome::Correct, Duration::ZERO)
+        } else if page.contains("you gave an answer too recently") {
+            let wait = wait.ok_or_else(|| eyre!("rate limited without the time left to wait"))?;
+            (Outcome::RateLimited, wait)
+        } else if page.contains("that's not the right answer") {
+            let outcome = if page.contains("answer is too high") {
+                Outcome::TooHigh
+            } else if page.contains("answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            };
+            // the server never takes another answer sooner than a minute after a wrong one
+            (outcome, wait.unwrap_or(Duration::from_secs(60)))
+        } else if page.contains("you don't seem to be solving the right level") {
+            (Outcome::AlreadySolved, Duration::ZERO)
+        } else {
+            bail!("the server's reply contains no known verdict");
+        };
+        Ok(Self { outcome, cooldown })
+    }
+}
+
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let rest = &text[text.find(start)? + start.len()..];
+    Some(&rest[..rest.find(end)?])
+}
+
+/// Finds how long a lowercased page asks to wait, from `please wait one minute`,
+/// `please wait 5 minutes` or `you have 4m 32s left to wait`.
+fn wait_time(page: &str) -> Result<Option<Duration>> {
+    if let Some(left) = between(page, "you have ", " left to wait") {
+        return parse_time_left(left).map(Some);
+    }
+    let minutes = match between(page, "please wait ", " minute") {
+        None => return Ok(None),
+        Some("one" | "a") => 1,
+        Some(minutes) => minutes
+            .parse()
+            .map_err(|_| eyre!("invalid wait time '{minutes}'"))?,
+    };
+    Ok(Some(Duration::from_secs(60 * minutes)))
+}
+
+/// Parses times like `1m 23s` or `45s`.
+fn parse_time_left(text: &str) -> Result<Duration> {
+    let mut seconds = 0;
+    for token in text.split_whitespace() {
+        let (number, unit) = token.split_at(token.len() - 1);
+        let number: u64 = number
+            .parse()
+            .map_err(|_| eyre!("invalid wait time '{text}'"))?;
+        seconds += match unit {
+            "h" => number * 3600,
+            "m" => number * 60,
+            "s" => number,
+            _ => bail!("invalid wait time '{text}'"),
+        };
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+/// One submission recorded in the [`Ledger`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attempt {
+    /// Unix time of the submission.
+    pub timestamp: u64,
+    pub part: Part,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Unix time before which the server will not accept another answer.
+    pub retry_after: u64,
+}
+
+/// Every answer submitted for a day, stored in the file `submissions` in the day's directory.
+///
+/// Each line holds the Unix time, the part, the answer, the outcome and the Unix time the
+/// cooldown ends, separated by tabs. Submissions are personal, so the file is not checked in.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Ledger {
+    attempts: Vec<Attempt>,
+}
+
+impl Ledger {
+    /// Reads the ledger of a day; a missing file means nothing was submitted yet.
+    pub fn load(day: &Day) -> Result<Self> {
+        let path = day.submissions_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text =
+            read_to_string(&path).wrap_err_with(|| format!("failed to read {}", path.display()))?;
+        Self::parse(&text).wrap_err_with(|| format!("invalid ledger in {}", path.display()))
+    }
+
+    /// Parses lines of tab-separated fields: timestamp, part, answer, outcome and the Unix time
+    /// the cooldown ends.
+    pub fn parse<'a>(text: &'a str) -> Result<Self, ParseError> {
+        let attempt = |cursor: &mut Cursor<'a>| {
+            Ok(Attempt {
+                timestamp: unsigned(cursor)?,
+                part: parsed_field(cursor)?,
+                answer: field(cursor)?.to_string(),
+                outcome: parsed_field(cursor)?,
+                retry_after: labelled("\t", unsigned)(cursor)?,
+            })
+        };
+
+        Ok(Self {
+            attempts: parse_all(text, lines(attempt))?,
+        })
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// Fails with the reason if submitting `answer` at Unix time `now` cannot succeed.
+    pub fn check(&self, part: Part, answer: &str, now: u64) -> Result<()> {
+        let attempts = || self.attempts.iter().filter(|attempt| attempt.part == part);
+
+        if let Some(solved) = attempts().find(|attempt| attempt.outcome == Outcome::Correct) {
+            bail!("{part} was already solved with {}", solved.answer);
+        }
+        if attempts().any(|attempt| attempt.outcome == Outcome::AlreadySolved) {
+            bail!("{part} was already solved on the server");
+        }
+        if let Some(rejected) =
+            attempts().find(|attempt| attempt.answer == answer && attempt.outcome.is_wrong())
+        {
+            bail!("{answer} was already rejected as {}", rejected.outcome);
+        }
+        if let Ok(value) = answer.parse::<i64>() {
+            for attempt in attempts() {
+                let Ok(known) = attempt.answer.parse::<i64>() else {
+                    continue;
+                };
+                match attempt.outcome {
+                    Outcome::TooHigh if value >= known => {
+                        bail!("{answer} cannot be right, {known} was already too high")
+                    }
+                    Outcome::TooLow if value <= known => {
+                        bail!("{answer} cannot be right, {known} was already too low")
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let retry_after = self
+            .attempts
+            .iter()
+            .map(|attempt| attempt.retry_after)
+            .max()
+            .unwrap_or(0);
+        if retry_after > now {
+            bail!(
+                "the server asked to wait another {}s before the next answer",
+                retry_after - now
+            );
+        }
+        Ok(())
+    }
+
+    /// Records an attempt and appends it to the ledger file of the day.
+    pub fn record(&mut self, day: &Day, attempt: Attempt) -> Result<()> {
+        let line = format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            attempt.timestamp,
+            attempt.part.number(),
+            attempt.answer,
+            attempt.outcome,
+            attempt.retry_after
+        );
+        let path = day.submissions_path();
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .wrap_err_with(|| format!("failed to write {}", path.display()))?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+/// Parses a tab and the field after it.
+fn field<'a>(cursor: &mut Cursor<'a>) -> Result<&'a str, ParseError> {
+    labelled("\t", take_while(|next| next != '\t'))(cursor)
+}
+
+/// Parses a tab and the field after it with the `FromStr` implementation of `T`.
+fn parsed_field<'a, T: FromStr<Err = eyre::Report>>(
+    cursor: &mut Cursor<'a>,
+) -> Result<T, ParseError> {
+    let field = field(cursor)?;
+    field
+        .parse()
+        .map_err(|error| cursor.error_at(field, format!("{error}")))
+}
+
+/// Solves a part on the day's input and submits the answer, unless the ledger rules it out.
+///
+/// Every attempt is recorded in the ledger, and a correct answer also becomes a known answer.
+pub fn submit(client: &Client, year: u16, day: &Day, part: Part) -> Result<Attempt> {
+    let input = InputSource::Default.read(day)?;
+    let answer = day.parse(&input)?.solve(part)?;
+    if answer.is_multiline() {
+        bail!(
+            "{part} has a multi-line answer, which has to be read and submitted by hand:\n{answer}"
+        );
+    }
+    let answer = answer.to_string();
+
+    let mut ledger = Ledger::load(day)?;
+    ledger
+        .check(part, &answer, unix_time())
+        .wrap_err_with(|| format!("not submitting {answer}"))?;
+
+    let reply = client.submit_answer(year, day.day, part, &answer)?;
+    let timestamp = unix_time();
+    let attempt = Attempt {
+        timestamp,
+        part,
+        answer,
+        outcome: reply.outcome,
+        retry_after: timestamp + reply.cooldown.as_secs(),
+    };
+    ledger.record(day, attempt.clone())?;
+
+    if attempt.outcome == Outcome::Correct {
+        let mut known = KnownAnswers::load(day)?;
+        known.insert("input", part, attempt.answer.clone());
+        known.save(day)?;
+    }
+    Ok(attempt)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        env::temp_dir,
+        fs::{create_dir_all, remove_dir_all, write},
+        process, thread,
+    };
+
+    use tiny_http::{Response, Server};
+
+    use super::*;
+    use crate::{Answer, Config, Solution};
+
+    struct Echo(String);
+
+    impl Solution for Echo {
+        fn parse(input: &str) -> Result<Self, ParseError> {
+            Ok(Echo(input.to_string()))
+        }
+
+        fn part_1(&self) -> Result<Answer> {
+            Ok(self.0.as_str().into())
+        }
+
+        fn part_2(&self) -> Result<Answer> {
+            Ok(self.0.as_str().into())
+        }
+    }
+
+    #[test]
+    fn replies() {
+        let reply = |page| Reply::parse(page).unwrap();
+        assert_eq!(
+            reply("<p>That's the right answer! You are one gold star closer.</p>").outcome,
+            Outcome::Correct
+        );
+        assert_eq!(
+            reply("<p>You don't seem to be solving the right level.  Did you already complete it?</p>").outcome,
+            Outcome::AlreadySolved
+        );
+        assert!(Reply::parse("<p>Not found</p>").is_err());
+    }
+
+    #[test]
+    fn wait_times() {
+        let reply = |page| Reply::parse(page).unwrap();
+        assert_eq!(
+            reply("<p>That's not the right answer; your answer is too high. Please wait one minute before trying again.</p>"),
+            Reply { outcome: Outcome::TooHigh, cooldown: Duration::from_secs(60) }
+        );
+        assert_eq!(
+            reply("<p>That's not the right answer. Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again.</p>"),
+            Reply { outcome: Outcome::Wrong, cooldown: Duration::from_secs(300) }
+        );
+        assert_eq!(
+            reply("<p>That's not the right answer; your answer is too low. You have 4m 32s left to wait.</p>"),
+            Reply { outcome: Outcome::TooLow, cooldown: Duration::from_secs(272) }
+        );
+        assert_eq!(
+            reply("<p>You gave an answer too recently. You have 1m 23s left to wait.</p>"),
+            Reply {
+                outcome: Outcome::RateLimited,
+                cooldown: Duration::from_secs(83)
+            }
+        );
+        assert_eq!(
+            reply("<p>You gave an answer too recently. You have 32s left to wait.</p>").cooldown,
+            Duration::from_secs(32)
+        );
+        assert!(Reply::parse("<p>You gave an answer too recently.</p>").is_err());
+        assert!(
+            Reply::parse("<p>That's not the right answer. Please wait many minutes.</p>").is_err()
+        );
+    }
+
+    #[test]
+    fn ledger() {
+        let text =
+            "100\t1\t50\ttoo low\t160\n200\t1\t90\ttoo high\t260\n300\t2\tCMZ\tcorrect\t300\n";
+        let ledger = Ledger::parse(text).unwrap();
+        assert_eq!(ledger.attempts()[1].outcome, Outcome::TooHigh);
+
+        assert!(ledger.check(Part::One, "70", 300).is_ok());
+        assert!(ledger.check(Part::One, "70", 200).is_err(), "cooldown");
+        assert!(ledger.check(Part::One, "50", 300).is_err(), "known wrong");
+        assert!(
+            ledger.check(Part::One, "95", 300).is_err(),
+            "above too high"
+        );
+        assert!(ledger.check(Part::One, "40", 300).is_err(), "below too low");
+        assert!(
+            ledger.check(Part::Two, "MCD", 300).is_err(),
+            "already solved"
+        );
+
+        let error = Ledger::parse("100\t1\t50\ttoo big\t160\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 10));
+        let error = Ledger::parse("100\t3\t50\twrong\t160\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+    }
+
+    #[test]
+    fn submits_once() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let handler = thread::spawn(move || {
+            // only the first request is answered, a second submission would fail
+            let request = server.recv().unwrap();
+            let url = request.url().to_string();
+            let page =
+                "<article><p>That's the right answer! You are one gold star closer.</p></article>";
+            request.respond(Response::from_string(page)).unwrap();
+            url
+        });
+        let client = Client::new(&Config {
+            session: Some("secret".to_string()),
+            base_url,
+        })
+        .unwrap();
+
+        let directory = temp_dir().join(format!("aoc-submit-{}", process::id()));
+        create_dir_all(&directory).unwrap();
+        write(directory.join("input"), "42").unwrap();
+        let directory = directory.to_string_lossy().into_owned().leak();
+        let day = Day::new::<Echo>(3, "Echo", directory);
+
+        let attempt = submit(&client, 2022, &day, Part::One).unwrap();
+        assert_eq!(attempt.outcome, Outcome::Correct);
+        let error = submit(&client, 2022, &day, Part::One).unwrap_err();
+        let ledger = Ledger::load(&day).unwrap();
+        let known = KnownAnswers::load(&day).unwrap();
+        remove_dir_all(&*directory).unwrap();
+
+        assert!(
+            format!("{error:#}").contains("Part 1 was already solved with 42"),
+            "{error:#}"
+        );
+        assert_eq!(ledger.attempts(), [attempt]);
+        assert_eq!(known.get("input", Part::One), Some("42"));
+        assert_eq!(handler.join().unwrap(), "/2022/day/3/answer");
+    }
+}
@@ -0,0 +1,132 @@
+use std::{fs::read_dir, path::Path};
+
+use eyre::{Context, Report, Result};
+use itertools::Itertools;
+use tracing::info_span;
+
+use crate::{Day, InputSource, KnownAnswers, Parameters, Part};
+
+/// The outcome of re-solving one known answer.
+#[derive(Debug)]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        expected: String,
+        actual: String,
+    },
+    /// Parsing or solving returned an error.
+    Failed(Report),
+    /// The input is not available, e.g. because puzzle inputs are not checked in.
+    Skipped(Report),
+}
+
+#[derive(Debug)]
+pub struct Verification {
+    pub input: String,
+    pub part: Part,
+    pub verdict: Verdict,
+}
+
+impl Verification {
+    /// Whether this answer no longer matches; skipped inputs do not count.
+    pub fn is_regression(&self) -> bool {
+        matches!(self.verdict, Verdict::Wrong { .. } | Verdict::Failed(_))
+    }
+}
+
+/// Whether an input file in a day's directory is an example, like `example` or `example2`.
+pub(crate) fn is_example(input: &str) -> bool {
+    input.starts_with("example")
+}
+
+/// Re-solves every known answer of a day, parsing each input only once.
+pub fn verify(day: &Day) -> Result<Vec<Verification>> {
+    verify_inputs(day, |_| true)
+}
+
+/// Re-solves the known answers of a day's examples, which are always checked in.
+pub fn verify_examples(day: &Day) -> Result<Vec<Verification>> {
+    verify_inputs(day, is_example)
+}
+
+/// The examples of a day that have known answers, in the order of its answers file.
+pub fn known_examples(day: &Day) -> Result<Vec<String>> {
+    let known = KnownAnswers::load(day)?;
+    Ok(known
+        .iter()
+        .map(|entry| entry.input.clone())
+        .filter(|input| is_example(input))
+        .unique()
+        .collect())
+}
+
+/// Lists the example files of a day that have no known answers, so nothing checks them.
+///
+/// Empty examples, like the one `aoc new` creates, are still to be filled in and not listed.
+pub fn unverified_examples(day: &Day) -> Result<Vec<String>> {
+    let known = KnownAnswers::load(day)?;
+    let entries =
+        read_dir(day.directory).wrap_err_with(|| format!("failed to list {}", day.directory))?;
+    let mut unverified = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if is_example(&name)
+            && entry.metadata()?.len() > 0
+            && known.iter().all(|entry| entry.input != name)
+        {
+            unverified.push(name);
+        }
+    }
+    unverified.sort();
+    Ok(unverified)
+}
+
+fn verify_inputs(day: &Day, include: impl Fn(&str) -> bool) -> Result<Vec<Verification>> {
+    let _day = info_span!("day", day = day.day).entered();
+    let known = KnownAnswers::load(day)?;
+    let mut verifications = Vec::new();
+
+    let inputs = known.iter().map(|entry| entry.input.as_str()).unique();
+    for input in inputs.filter(|input| include(input)) {
+        let entries = known.iter().filter(|entry| entry.input == input);
+        let source = InputSource::Path(Path::new(day.directory).join(input));
+
+        let solution = match source.read(day) {
+            // `aoc new` starts with an empty example and a placeholder answer for it
+            Ok(text) if text.is_empty() && is_example(input) => continue,
+            Ok(text) => info_span!("parse", input)
+                .in_scope(|| day.parse_with(&text, Some(input), &Parameters::default())),
+            Err(error) => {
+                verifications.extend(entries.map(|entry| Verification {
+                    input: input.to_string(),
+                    part: entry.part,
+                    verdict: Verdict::Skipped(eyre::eyre!("{error}")),
+                }));
+                continue;
+            }
+        };
+
+        for entry in entries {
+            let span = info_span!("solve", input, part = entry.part.number());
+            let verdict = match span
+                .in_scope(|| solution.as_ref().map(|solution| solution.solve(entry.part)))
+            {
+                Ok(Ok(answer)) if answer.to_string() == entry.answer => Verdict::Correct,
+                Ok(Ok(answer)) => Verdict::Wrong {
+                    expected: entry.answer.clone(),
+                    actual: answer.to_string(),
+                },
+                Ok(Err(error)) => Verdict::Failed(error),
+                Err(error) => Verdict::Failed(eyre::eyre!("{error}")),
+            };
+            verifications.push(Verification {
+                input: input.to_string(),
+                part: entry.part,
+                verdict,
+            });
+        }
+    }
+
+    Ok(verifications)
+}
@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day_2022_05::SupplyStacks;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = SupplyStacks::parse(input);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day_2022_11::MonkeyInTheMiddle;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = MonkeyInTheMiddle::parse(input);
+});
@@ -0,0 +1,15 @@
+#!/bin/sh
+# Seeds the corpus of every fuzz target with the example inputs of its day, e.g.
+#
+#     fuzz/seed_corpus.sh && cargo +nightly fuzz run day_13_packets
+set -e
+cd "$(dirname "$0")"
+
+for target in fuzz_targets/*.rs; do
+    target=$(basename "$target" .rs)
+    day=$(echo "$target" | cut -d_ -f2)
+    mkdir -p "corpus/$target"
+    for example in ../2022/day-"$day"/example*; do
+        cp "$example" "corpus/$target/$(basename "$example")"
+    done
+done